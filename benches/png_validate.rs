@@ -1,5 +1,23 @@
 use argos::validate::png;
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use flate2::Compression;
+use flate2::write::ZlibEncoder;
+use std::io::Write;
+
+const SIDE: u32 = 64;
+
+fn compressed_scanlines() -> Vec<u8> {
+    let mut raw = Vec::new();
+    for row in 0..SIDE {
+        raw.push(0);
+        for col in 0..SIDE {
+            raw.extend_from_slice(&[row as u8, col as u8, (row ^ col) as u8]);
+        }
+    }
+    let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&raw).expect("compress");
+    encoder.finish().expect("finish")
+}
 
 fn valid_png() -> Vec<u8> {
     let signature = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
@@ -8,7 +26,11 @@ fn valid_png() -> Vec<u8> {
 
     let ihdr_len = 13u32;
     let ihdr_type = b"IHDR";
-    let ihdr_data = [0x00; 13];
+    let mut ihdr_data = [0x00; 13];
+    ihdr_data[0..4].copy_from_slice(&SIDE.to_be_bytes());
+    ihdr_data[4..8].copy_from_slice(&SIDE.to_be_bytes());
+    ihdr_data[8] = 8;
+    ihdr_data[9] = 2;
     let ihdr_crc = {
         let mut h = crc32fast::Hasher::new();
         h.update(ihdr_type);
@@ -20,9 +42,9 @@ fn valid_png() -> Vec<u8> {
     data.extend_from_slice(&ihdr_data);
     data.extend_from_slice(&ihdr_crc.to_be_bytes());
 
-    let idat_len = 10u32;
     let idat_type = b"IDAT";
-    let idat_data = [0x78, 0x9C, 0x63, 0x60, 0x00, 0x00, 0x00, 0x02, 0x00, 0x01];
+    let idat_data = compressed_scanlines();
+    let idat_len = idat_data.len() as u32;
     let idat_crc = {
         let mut h = crc32fast::Hasher::new();
         h.update(idat_type);
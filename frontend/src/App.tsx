@@ -82,6 +82,7 @@ export default function App() {
                 device={device()}
                 bytesRecovered={session.bytesRecovered()}
                 elapsedMs={session.elapsedMs()}
+                phaseElapsedMs={session.phaseElapsedMs()}
                 canStart={canStart()}
                 onStart={handleStart}
                 onCancel={() => void session.cancel()}
@@ -9,7 +9,7 @@ import {
   SearchIcon,
   StopIcon,
 } from './icons';
-import type { DeviceInfo, ProgressEvent } from '../lib/bridge';
+import type { DeviceInfo, ProgressEvent, ProgressPhase } from '../lib/bridge';
 import type { SessionPhase } from '../lib/recovery';
 import {
   formatBytes,
@@ -23,6 +23,7 @@ interface StatusPanelProps {
   device: DeviceInfo | null;
   bytesRecovered: number;
   elapsedMs: number;
+  phaseElapsedMs: number;
   canStart: boolean;
   onStart: () => void;
   onCancel: () => void;
@@ -38,18 +39,29 @@ const PHASE_LABEL: Record<SessionPhase, string> = {
   failed: 'Failed',
 };
 
+const PROGRESS_PHASE_LABEL: Record<ProgressPhase, string> = {
+  scanning: 'Scanning',
+  extracting: 'Extracting',
+};
+
 const RATIO_THRESHOLD = 0.005;
 const ELAPSED_THRESHOLD_MS = 1500;
 
 export default function StatusPanel(props: StatusPanelProps) {
-  const totalBytes = () => props.device?.size_bytes ?? 0;
+  const totalBytes = () =>
+    props.progress?.total_bytes ?? props.device?.size_bytes ?? 0;
 
   const ratio = (): number | null => {
     if (props.phase === 'completed') return 1;
     if (props.phase === 'idle') return 0;
     if (props.phase === 'starting') return null;
+    const progress = props.progress;
+    if (progress?.phase === 'extracting') {
+      if (progress.candidates_found <= 0) return 1;
+      return progress.candidates_extracted / progress.candidates_found;
+    }
     const total = totalBytes();
-    const scanned = props.progress?.bytes_scanned ?? 0;
+    const scanned = progress?.bytes_scanned ?? 0;
     if (total <= 0) return null;
     return scanned / total;
   };
@@ -57,8 +69,16 @@ export default function StatusPanel(props: StatusPanelProps) {
   const estimatedTotalMs = (): number | null => {
     const r = ratio();
     if (r === null || r < RATIO_THRESHOLD) return null;
-    if (props.elapsedMs < ELAPSED_THRESHOLD_MS) return null;
-    return props.elapsedMs / r;
+    if (props.phaseElapsedMs < ELAPSED_THRESHOLD_MS) return null;
+    return props.elapsedMs - props.phaseElapsedMs + props.phaseElapsedMs / r;
+  };
+
+  const phaseLabel = (): string => {
+    const progress = props.progress;
+    if (props.phase === 'running' && progress) {
+      return PROGRESS_PHASE_LABEL[progress.phase];
+    }
+    return PHASE_LABEL[props.phase];
   };
 
   const remainingMs = (): number | null => {
@@ -100,7 +120,7 @@ export default function StatusPanel(props: StatusPanelProps) {
       <div class="status-hero">
         <CircularProgress ratio={ratio()} size={120} stroke={8} />
         <div class="status-hero-text">
-          <span class="hero-label">{PHASE_LABEL[props.phase]}</span>
+          <span class="hero-label">{phaseLabel()}</span>
           <span class="hero-device">{props.device?.path ?? '—'}</span>
           <div class="hero-times">
             <div class="hero-time">
@@ -12,10 +12,15 @@ export interface DeviceInfo {
   model: string | null;
 }
 
+export type ProgressPhase = 'scanning' | 'extracting';
+
 export interface ProgressEvent {
   session_id: number;
+  phase: ProgressPhase;
   bytes_scanned: number;
+  total_bytes: number;
   candidates_found: number;
+  candidates_extracted: number;
   artifacts_recovered: number;
 }
 
@@ -27,6 +32,11 @@ export interface ArtifactEvent {
   score: number;
 }
 
+export interface SessionWarningEvent {
+  session_id: number;
+  message: string;
+}
+
 export type SessionCompletionStatus = 'ok' | 'cancelled' | 'failed';
 
 export interface SessionCompletedEvent {
@@ -42,6 +52,8 @@ export type BridgeErrorKind =
   | 'pattern_build'
   | 'validation'
   | 'audit_serialization'
+  | 'source_changed'
+  | 'output_full'
   | 'denied';
 
 export interface BridgeError {
@@ -77,6 +89,13 @@ export const onArtifact = (
 ): Promise<UnlistenFn> =>
   listen<ArtifactEvent>('artifact', (event) => handler(event.payload));
 
+export const onSessionWarning = (
+  handler: (event: SessionWarningEvent) => void,
+): Promise<UnlistenFn> =>
+  listen<SessionWarningEvent>('session_warning', (event) =>
+    handler(event.payload),
+  );
+
 export const onSessionCompleted = (
   handler: (event: SessionCompletedEvent) => void,
 ): Promise<UnlistenFn> =>
@@ -97,6 +116,8 @@ const ERROR_MESSAGES: Record<BridgeErrorKind, string> = {
   pattern_build: 'Failed to build the search patterns used for carving.',
   validation: 'Recovered bytes failed structural validation and were discarded.',
   audit_serialization: 'Failed to serialize the audit trail.',
+  source_changed: 'The source device changed while it was being scanned. Reports were written, but the recovered files may not match the evidence.',
+  output_full: 'The output drive ran out of space. The file being written was removed, and the scan report lists the candidates that were not extracted. Free some space or choose another output folder, then run the session again.',
   denied: 'The selected path is outside the allowed scope or the session is no longer valid.',
 };
 
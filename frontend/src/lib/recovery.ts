@@ -8,6 +8,7 @@ import {
   onArtifact,
   onProgress,
   onSessionCompleted,
+  onSessionWarning,
   startRecovery,
 } from './bridge';
 
@@ -24,6 +25,7 @@ export interface RecoverySession {
   progress: () => ProgressEvent | null;
   bytesRecovered: () => number;
   elapsedMs: () => number;
+  phaseElapsedMs: () => number;
   errorMessage: () => string | null;
   warningMessage: () => string | null;
   start: (source: string, output: string) => Promise<void>;
@@ -47,6 +49,7 @@ export function createRecoverySession(): RecoverySession {
   const [progress, setProgress] = createSignal<ProgressEvent | null>(null);
   const [bytesRecovered, setBytesRecovered] = createSignal(0);
   const [elapsedMs, setElapsedMs] = createSignal(0);
+  const [phaseStartedMs, setPhaseStartedMs] = createSignal(0);
   const [errorMessage, setErrorMessage] = createSignal<string | null>(null);
   const [warningMessage, setWarningMessage] = createSignal<string | null>(null);
   const [sessionId, setSessionId] = createSignal<number | null>(null);
@@ -54,6 +57,7 @@ export function createRecoverySession(): RecoverySession {
   let unlistenProgress: UnlistenFn | undefined;
   let unlistenArtifact: UnlistenFn | undefined;
   let unlistenCompleted: UnlistenFn | undefined;
+  let unlistenWarning: UnlistenFn | undefined;
   let startedAt = 0;
   let tickHandle: number | undefined;
 
@@ -78,10 +82,19 @@ export function createRecoverySession(): RecoverySession {
       unlistenProgress?.(),
       unlistenArtifact?.(),
       unlistenCompleted?.(),
+      unlistenWarning?.(),
     ]);
     unlistenProgress = undefined;
     unlistenArtifact = undefined;
     unlistenCompleted = undefined;
+    unlistenWarning = undefined;
+  };
+
+  const addWarning = (message: string | undefined) => {
+    if (!message) return;
+    setWarningMessage((current) =>
+      current ? `${current} ${message}` : message,
+    );
   };
 
   const reset = () => {
@@ -92,6 +105,7 @@ export function createRecoverySession(): RecoverySession {
       setProgress(null);
       setBytesRecovered(0);
       setElapsedMs(0);
+      setPhaseStartedMs(0);
       setErrorMessage(null);
       setWarningMessage(null);
       setSessionId(null);
@@ -106,14 +120,26 @@ export function createRecoverySession(): RecoverySession {
       setProgress(null);
       setBytesRecovered(0);
       setElapsedMs(0);
+      setPhaseStartedMs(0);
       setErrorMessage(null);
+      setWarningMessage(null);
     });
 
     try {
-      unlistenProgress = await onProgress((event) => setProgress(event));
+      unlistenProgress = await onProgress((event) => {
+        batch(() => {
+          if (progress()?.phase !== event.phase) {
+            setPhaseStartedMs(elapsedMs());
+          }
+          setProgress(event);
+        });
+      });
       unlistenArtifact = await onArtifact((event) => {
         setBytesRecovered((b) => b + event.length);
       });
+      unlistenWarning = await onSessionWarning((event) => {
+        addWarning(event.message);
+      });
       unlistenCompleted = await onSessionCompleted((event) => {
         if (sessionId() !== event.session_id) return;
         stopTick();
@@ -130,7 +156,7 @@ export function createRecoverySession(): RecoverySession {
       const response = await startRecovery(source, output);
       batch(() => {
         setSessionId(response.session_id);
-        setWarningMessage(response.warning ?? null);
+        addWarning(response.warning);
         setPhase('running');
       });
       startTick();
@@ -169,6 +195,7 @@ export function createRecoverySession(): RecoverySession {
     progress,
     bytesRecovered,
     elapsedMs,
+    phaseElapsedMs: () => elapsedMs() - phaseStartedMs(),
     errorMessage,
     warningMessage,
     start,
@@ -0,0 +1,8 @@
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let text = String::from_utf8_lossy(data);
+    if let Some(blocks) = argos::io::ddrescue::parse(&text) {
+        let _ = argos::io::ddrescue::unreadable_ranges(&blocks);
+    }
+});
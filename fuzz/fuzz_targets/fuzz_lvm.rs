@@ -0,0 +1,7 @@
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    if let Some(area) = argos::io::lvm::metadata_area(data) {
+        let _ = argos::io::lvm::logical_volumes(&area, data, 0);
+    }
+});
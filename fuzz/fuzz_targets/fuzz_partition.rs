@@ -0,0 +1,5 @@
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = argos::io::partition::parse(data);
+});
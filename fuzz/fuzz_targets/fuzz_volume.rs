@@ -0,0 +1,6 @@
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = argos::io::volume::probe(data);
+    let _ = argos::io::volume::luks_header(data);
+});
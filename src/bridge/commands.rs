@@ -42,9 +42,9 @@ fn same_device_warning(source: &Path, output: &Path) -> Option<String> {
         use std::os::unix::fs::{FileTypeExt, MetadataExt};
         let source_meta = std::fs::metadata(source).ok()?;
         let output_meta = std::fs::metadata(output).ok()?;
-        let source_dev = if source_meta.file_type().is_block_device()
-            || source_meta.file_type().is_char_device()
-        {
+        let source_is_device =
+            source_meta.file_type().is_block_device() || source_meta.file_type().is_char_device();
+        let source_dev = if source_is_device {
             source_meta.rdev()
         } else {
             source_meta.dev()
@@ -56,6 +56,16 @@ fn same_device_warning(source: &Path, output: &Path) -> Option<String> {
                     .into(),
             );
         }
+        #[cfg(target_os = "linux")]
+        if source_is_device
+            && devices::whole_disk(source_dev)
+                .is_some_and(|disk| devices::whole_disk(output_dev) == Some(disk))
+        {
+            return Some(
+                "Output is on the same physical disk as the source. Writing recovered data to the analyzed disk is not recommended because it may overwrite recoverable data."
+                    .into(),
+            );
+        }
     }
     #[cfg(windows)]
     {
@@ -82,10 +92,10 @@ pub async fn start_recovery(
     let source = ScopedPath::new(&request.source, &source_scopes)?;
     let output = ScopedPath::new(&request.output, &output_scopes)?;
 
-    let warning = same_device_warning(source.as_path(), output.as_path());
+    let same_device = same_device_warning(source.as_path(), output.as_path());
 
     let session_id = manager.create();
-    if warning.is_some() {
+    if same_device.is_some() {
         tracing::warn!(same_device_warning = true, session_id);
     }
     let session = manager.get(session_id).ok_or_else(|| BridgeError {
@@ -112,7 +122,7 @@ pub async fn start_recovery(
 
     Ok(StartResponse {
         session_id,
-        warning,
+        warning: same_device,
     })
 }
 
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 
+use crate::custody::report::DeviceIdentity;
 use crate::error::ArgosError;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,23 +38,23 @@ fn list_linux() -> Result<Vec<DeviceInfo>, ArgosError> {
     let mut devices: Vec<DeviceInfo> = entries
         .filter_map(|entry| entry.ok())
         .filter_map(|entry| device_from_sysfs(&entry))
+        .flat_map(|(device, base)| {
+            let partitions = partitions_from_sysfs(&device, &base);
+            std::iter::once(device).chain(partitions)
+        })
         .collect();
     devices.sort_by(|a, b| a.name.cmp(&b.name));
     Ok(devices)
 }
 
 #[cfg(target_os = "linux")]
-fn device_from_sysfs(entry: &std::fs::DirEntry) -> Option<DeviceInfo> {
+fn device_from_sysfs(entry: &std::fs::DirEntry) -> Option<(DeviceInfo, std::path::PathBuf)> {
     let name = entry.file_name().to_string_lossy().into_owned();
     if is_virtual_block(&name) {
         return None;
     }
     let base = entry.path();
-    let size_sectors: u64 = read_trim(base.join("size"))?.parse().ok()?;
-    let size_bytes = size_sectors.checked_mul(512)?;
-    if size_bytes == 0 {
-        return None;
-    }
+    let size_bytes = sysfs_size_bytes(&base)?;
     let class = match read_trim(base.join("queue/rotational")).as_deref() {
         Some("1") => DeviceClassDto::Hdd,
         Some("0") => DeviceClassDto::Ssd,
@@ -63,16 +64,94 @@ fn device_from_sysfs(entry: &std::fs::DirEntry) -> Option<DeviceInfo> {
         .map(|s| s == "1")
         .unwrap_or(false);
     let model = read_trim(base.join("device/model")).filter(|s| !s.is_empty());
-    Some(DeviceInfo {
-        path: format!("/dev/{name}"),
-        name,
-        size_bytes,
-        class,
-        removable,
-        model,
+    Some((
+        DeviceInfo {
+            path: format!("/dev/{name}"),
+            name,
+            size_bytes,
+            class,
+            removable,
+            model,
+        },
+        base,
+    ))
+}
+
+#[cfg(target_os = "linux")]
+fn partitions_from_sysfs(parent: &DeviceInfo, base: &std::path::Path) -> Vec<DeviceInfo> {
+    let Ok(entries) = std::fs::read_dir(base) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().join("partition").is_file())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let size_bytes = sysfs_size_bytes(&entry.path())?;
+            Some(DeviceInfo {
+                path: format!("/dev/{name}"),
+                name,
+                size_bytes,
+                class: parent.class.clone(),
+                removable: parent.removable,
+                model: parent.model.clone(),
+            })
+        })
+        .collect()
+}
+
+pub fn identity(source: &std::path::Path) -> DeviceIdentity {
+    #[cfg(target_os = "linux")]
+    {
+        identity_linux(source).unwrap_or_default()
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = source;
+        DeviceIdentity::default()
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn identity_linux(source: &std::path::Path) -> Option<DeviceIdentity> {
+    use std::os::unix::fs::{FileTypeExt, MetadataExt};
+    let meta = std::fs::metadata(source).ok()?;
+    if !meta.file_type().is_block_device() {
+        return None;
+    }
+    let disk = whole_disk(meta.rdev())?;
+    let device = disk.join("device");
+    let attribute = |candidates: &[std::path::PathBuf]| {
+        candidates
+            .iter()
+            .find_map(|path| read_trim(path).filter(|value| !value.is_empty()))
+    };
+    Some(DeviceIdentity {
+        model: attribute(&[device.join("model")]),
+        serial: attribute(&[device.join("serial"), disk.join("serial")]),
+        firmware: attribute(&[device.join("firmware_rev"), device.join("rev")]),
+        wwn: attribute(&[disk.join("wwid"), device.join("wwid")]),
     })
 }
 
+#[cfg(target_os = "linux")]
+pub fn whole_disk(dev: u64) -> Option<std::path::PathBuf> {
+    let major = rustix::fs::major(dev);
+    let minor = rustix::fs::minor(dev);
+    let node = std::fs::canonicalize(format!("/sys/dev/block/{major}:{minor}")).ok()?;
+    if node.join("partition").exists() {
+        node.parent().map(std::path::Path::to_path_buf)
+    } else {
+        Some(node)
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn sysfs_size_bytes(base: &std::path::Path) -> Option<u64> {
+    let size_sectors: u64 = read_trim(base.join("size"))?.parse().ok()?;
+    size_sectors.checked_mul(512).filter(|&bytes| bytes > 0)
+}
+
 #[cfg(target_os = "linux")]
 fn is_virtual_block(name: &str) -> bool {
     name.starts_with("loop")
@@ -14,6 +14,8 @@ pub enum BridgeErrorKind {
     PatternBuild,
     Validation,
     AuditSerialization,
+    SourceChanged,
+    OutputFull,
     Denied,
 }
 
@@ -38,11 +40,22 @@ impl From<crate::error::ArgosError> for BridgeError {
             ArgosError::PatternBuild(_) => {
                 (BridgeErrorKind::PatternBuild, "pattern build failed".into())
             }
-            ArgosError::Validation { kind } => (BridgeErrorKind::Validation, format!("{kind}")),
+            ArgosError::Validation { kind, offset } => (
+                BridgeErrorKind::Validation,
+                format!("{kind} at byte {offset}"),
+            ),
             ArgosError::AuditSerialization(_) => (
                 BridgeErrorKind::AuditSerialization,
                 "audit serialization failed".into(),
             ),
+            ArgosError::SourceChanged => (
+                BridgeErrorKind::SourceChanged,
+                "source fingerprint changed during the session".into(),
+            ),
+            ArgosError::OutputFull => (
+                BridgeErrorKind::OutputFull,
+                "output filesystem ran out of space during extraction".into(),
+            ),
         };
         Self { kind, detail }
     }
@@ -65,11 +78,21 @@ pub struct CancelRequest {
     pub session_id: u64,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressPhase {
+    Scanning,
+    Extracting,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProgressEvent {
     pub session_id: u64,
+    pub phase: ProgressPhase,
     pub bytes_scanned: u64,
+    pub total_bytes: u64,
     pub candidates_found: u64,
+    pub candidates_extracted: u64,
     pub artifacts_recovered: u64,
 }
 
@@ -97,6 +120,12 @@ pub struct ArtifactEvent {
     pub score: f32,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionWarningEvent {
+    pub session_id: u64,
+    pub message: String,
+}
+
 pub struct ScopedPath {
     inner: PathBuf,
 }
@@ -1,23 +1,46 @@
 use std::path::Path;
 use std::sync::atomic::Ordering;
+use std::time::{Instant, SystemTime};
 
-use memmap2::{Mmap, MmapOptions};
+use memmap2::{Advice, Mmap, MmapOptions};
 use rayon::prelude::*;
 use tauri::{AppHandle, Emitter};
 
 use crate::bridge::{
-    ArtifactEvent, BridgeError, ProgressEvent, Session, SessionCompletedEvent, SessionStatus,
+    ArtifactEvent, BridgeError, ProgressEvent, ProgressPhase, Session, SessionCompletedEvent,
+    SessionStatus, SessionWarningEvent, devices,
 };
+use crate::carve::hdd::HddScan;
+use crate::carve::hdd::cluster::ClusterEstimate;
 use crate::carve::ssd::Scanner;
 use crate::carve::{Candidate, DeviceClass, ImageFormat};
-use crate::custody::{AuditEntry, AuditLog, BadSectorMap, Operation, Status};
+use crate::custody::dfxml::{ByteRun, DfxmlDocument, DfxmlSource, FileObject};
+use crate::custody::gallery::Gallery;
+use crate::custody::manifest::{Fragment, Manifest, ManifestEntry, RecoveryMethod};
+use crate::custody::report::{
+    BadSectorSummary, CandidateRecord, CandidateSummary, DeviceFingerprint, DeviceIdentity,
+    DeviceSummary, OutputSpace, Rejection, SCHEMA_VERSION, ScanReport, ScanTiming, SourceDigest,
+};
+use crate::custody::{
+    AuditEntry, AuditLog, BadSectorMap, Operation, Status, StreamDigest, unix_seconds,
+    unreadable_overlap,
+};
 use crate::error::ArgosError;
 use crate::io::OutputSink;
-use crate::io::{AlignedBuf, BlockReader, SourceDevice};
-use crate::reassemble::reassemble_ssd;
-use crate::validate;
+use crate::io::ddrescue;
+use crate::io::encryption;
+use crate::io::lvm;
+use crate::io::partition::{self, PartitionTable};
+use crate::io::volume::{self, Volume};
+use crate::io::{AlignedBuf, BlockReader, SourceDevice, read_head};
+use crate::reassemble::{Artifact, reassemble_ssd};
+use crate::validate::png::Animation;
+use crate::validate::{self, Inspection};
 
 const MAX_EXTRACTION_BYTES: usize = 64 * 1024 * 1024;
+const EXTRACTION_BATCH_BYTES: u64 = 256 * 1024 * 1024;
+const FINGERPRINT_WINDOW: u64 = 1024 * 1024;
+const DIGEST_QUEUE: usize = 4;
 
 #[derive(Debug)]
 pub struct RecoveryReport {
@@ -27,6 +50,7 @@ pub struct RecoveryReport {
     pub recovered_files: Vec<String>,
     pub progress_events: Vec<ProgressEvent>,
     pub artifact_events: Vec<ArtifactEvent>,
+    pub warning_events: Vec<SessionWarningEvent>,
 }
 
 pub fn run(
@@ -46,12 +70,15 @@ pub fn run(
         |event| {
             app.emit("artifact", event).ok();
         },
+        |event| {
+            app.emit("session_warning", event).ok();
+        },
     )?;
     Ok(())
 }
 
 pub fn run_test(source_path: &Path, output_path: &Path) -> Result<RecoveryReport, ArgosError> {
-    run_test_with_class(source_path, output_path, None)
+    run_test_with_class(source_path, output_path, None, None)
 }
 
 pub fn run_test_with_device_class(
@@ -59,13 +86,28 @@ pub fn run_test_with_device_class(
     output_path: &Path,
     device_class: DeviceClass,
 ) -> Result<RecoveryReport, ArgosError> {
-    run_test_with_class(source_path, output_path, Some(device_class))
+    run_test_with_class(source_path, output_path, Some(device_class), None)
+}
+
+pub fn run_test_cancelled_after_artifacts(
+    source_path: &Path,
+    output_path: &Path,
+    device_class: DeviceClass,
+    artifacts: usize,
+) -> Result<RecoveryReport, ArgosError> {
+    run_test_with_class(
+        source_path,
+        output_path,
+        Some(device_class),
+        Some(artifacts),
+    )
 }
 
 fn run_test_with_class(
     source_path: &Path,
     output_path: &Path,
     forced_device_class: Option<DeviceClass>,
+    cancel_after_artifacts: Option<usize>,
 ) -> Result<RecoveryReport, ArgosError> {
     let session = crate::bridge::Session {
         id: 0,
@@ -78,6 +120,7 @@ fn run_test_with_class(
         recovered_files: Vec::new(),
         progress_events: Vec::new(),
         artifact_events: Vec::new(),
+        warning_events: Vec::new(),
     };
 
     run_with_callbacks(
@@ -97,7 +140,11 @@ fn run_test_with_class(
                 event.format, event.offset, event.length, event.score
             ));
             report.artifact_events.push(event);
+            if cancel_after_artifacts.is_some_and(|limit| report.artifact_events.len() >= limit) {
+                session.cancel.store(true, Ordering::Relaxed);
+            }
         },
+        |event| report.warning_events.push(event),
     )?;
 
     Ok(report)
@@ -106,6 +153,7 @@ fn run_test_with_class(
 fn open_extraction_mmap(source_path: &Path, size: u64) -> Result<Mmap, ArgosError> {
     let file = std::fs::File::open(source_path)?;
     let mmap = unsafe { MmapOptions::new().len(size as usize).map(&file)? };
+    mmap.advise(Advice::Sequential)?;
     Ok(mmap)
 }
 
@@ -132,11 +180,435 @@ fn read_artifact_bytes(
     }
 }
 
-fn extension_for(format: ImageFormat) -> &'static str {
-    match format {
-        ImageFormat::Jpeg => "jpg",
-        ImageFormat::Png => "png",
+#[derive(Debug, Clone, Copy)]
+enum Derivation {
+    Carved,
+    Truncated,
+    CrcRepaired,
+}
+
+impl Derivation {
+    fn status(self) -> Status {
+        match self {
+            Derivation::Truncated => Status::Partial,
+            Derivation::Carved | Derivation::CrcRepaired => Status::Ok,
+        }
+    }
+
+    fn suffix(self) -> &'static str {
+        match self {
+            Derivation::Carved => "",
+            Derivation::Truncated => "_partial",
+            Derivation::CrcRepaired => "_repaired",
+        }
+    }
+
+    fn method(self) -> RecoveryMethod {
+        match self {
+            Derivation::Carved => RecoveryMethod::Carved,
+            Derivation::Truncated => RecoveryMethod::Truncated,
+            Derivation::CrcRepaired => RecoveryMethod::CrcRepaired,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct RecoveredOutput {
+    offset: u64,
+    length: u64,
+    format: ImageFormat,
+    score: f32,
+    derivation: Derivation,
+    bytes: Vec<u8>,
+    hash: [u8; 32],
+    md5: [u8; 16],
+    sha1: [u8; 20],
+    dimensions: Option<(u32, u32)>,
+    animation: Option<Animation>,
+    icc_profile_intact: Option<bool>,
+    icc_missing_segments: Vec<u8>,
+    quality: Option<u8>,
+    dropped_chunks: Vec<String>,
+    chunks_repaired: u64,
+}
+
+impl RecoveredOutput {
+    fn new(
+        offset: u64,
+        length: u64,
+        format: ImageFormat,
+        derivation: Derivation,
+        bytes: Vec<u8>,
+        inspection: Inspection,
+    ) -> Self {
+        let hash = crate::custody::hash(&bytes);
+        let md5 = crate::custody::md5(&bytes);
+        let sha1 = crate::custody::sha1(&bytes);
+        let icc_profile_intact = inspection
+            .icc_profile
+            .as_ref()
+            .map(|icc| icc.bytes.is_some());
+        let icc_missing_segments = inspection
+            .icc_profile
+            .map(|icc| icc.missing_segments)
+            .unwrap_or_default();
+        Self {
+            offset,
+            length,
+            format,
+            score: inspection.score,
+            derivation,
+            bytes,
+            hash,
+            md5,
+            sha1,
+            dimensions: inspection.dimensions,
+            animation: inspection.animation,
+            icc_profile_intact,
+            icc_missing_segments,
+            quality: inspection.quality,
+            dropped_chunks: Vec::new(),
+            chunks_repaired: 0,
+        }
+    }
+
+    fn file_object(&self, filename: String) -> FileObject {
+        FileObject {
+            filename,
+            filesize: self.bytes.len() as u64,
+            byte_runs: vec![ByteRun {
+                file_offset: 0,
+                img_offset: self.offset,
+                len: self.length,
+            }],
+            md5: self.md5,
+            sha1: self.sha1,
+            sha256: self.hash,
+            carve_method: self.derivation.method(),
+        }
+    }
+
+    fn manifest_entry(
+        &self,
+        file: String,
+        source_id: String,
+        unreadable_bytes: u64,
+    ) -> ManifestEntry {
+        ManifestEntry {
+            file,
+            source_id,
+            offset: self.offset,
+            fragments: vec![Fragment {
+                offset: self.offset,
+                length: self.length,
+            }],
+            format: self.format,
+            method: self.derivation.method(),
+            confidence: self.score,
+            width: self.dimensions.map(|(w, _)| w),
+            height: self.dimensions.map(|(_, h)| h),
+            frames: self.animation.map(|a| a.frames),
+            duration_ms: self.animation.map(|a| a.duration_ms),
+            icc_profile_intact: self.icc_profile_intact,
+            icc_missing_segments: self.icc_missing_segments.clone(),
+            quality: self.quality,
+            sha256: hex::encode(self.hash),
+            unreadable_bytes,
+            dropped_chunks: self.dropped_chunks.clone(),
+            chunks_repaired: self.chunks_repaired,
+            recovered_at: unix_seconds(SystemTime::now()),
+        }
+    }
+
+    fn file_name(&self) -> String {
+        format!(
+            "{}_{}_{}_{:.2}{}.{}",
+            hex::encode(&self.hash[..4]),
+            self.offset,
+            self.length,
+            self.score,
+            self.derivation.suffix(),
+            self.format.extension(),
+        )
+    }
+}
+
+fn inspect(format: ImageFormat, bytes: &[u8]) -> Inspection {
+    let inspection = match format {
+        ImageFormat::Jpeg => validate::jpeg::inspect(bytes),
+        ImageFormat::Png => validate::png::inspect(bytes),
+    };
+    inspection.unwrap_or_default()
+}
+
+fn rejection_for(artifact: &Artifact, bytes: &[u8]) -> Option<Rejection> {
+    let checked = match artifact.format {
+        ImageFormat::Jpeg => validate::jpeg::check_structure(bytes),
+        ImageFormat::Png => validate::png::parse_chunks(bytes).map(|_| ()),
+    };
+    match checked.map_err(|e| e.with_offset(artifact.offset)) {
+        Err(ArgosError::Validation { kind, offset }) => Some(Rejection { kind, offset }),
+        _ => None,
+    }
+}
+
+fn derived_output(
+    artifact: &Artifact,
+    bytes: &[u8],
+    inspection: &Inspection,
+) -> Option<RecoveredOutput> {
+    if inspection.score >= 1.0 {
+        return None;
+    }
+    match artifact.format {
+        ImageFormat::Jpeg => match validate::jpeg::truncate_to_last_valid_restart(bytes) {
+            Some(truncation) => verified_output(
+                artifact,
+                truncation.bytes,
+                truncation.source_length,
+                Derivation::Truncated,
+                1.0,
+            ),
+            None => {
+                let truncation = validate::jpeg::truncate_to_last_complete_scan(bytes)?;
+                verified_output(
+                    artifact,
+                    truncation.bytes,
+                    truncation.source_length,
+                    Derivation::Truncated,
+                    validate::jpeg::NON_BASELINE_SCORE,
+                )
+            }
+        },
+        ImageFormat::Png => validate::png::repair_crc(bytes, inspection)
+            .and_then(|repair| {
+                let mut output = verified_output(
+                    artifact,
+                    repair.bytes,
+                    bytes.len(),
+                    Derivation::CrcRepaired,
+                    1.0,
+                )?;
+                output.dropped_chunks = chunk_names(&repair.dropped);
+                output.chunks_repaired = repair.chunks_repaired as u64;
+                Some(output)
+            })
+            .or_else(|| {
+                let repair = validate::png::repair_truncated(bytes, inspection)?;
+                let mut output = verified_output(
+                    artifact,
+                    repair.bytes,
+                    repair.source_length,
+                    Derivation::Truncated,
+                    1.0,
+                )?;
+                output.dropped_chunks = chunk_names(&repair.dropped);
+                Some(output)
+            }),
+    }
+}
+
+fn verified_output(
+    artifact: &Artifact,
+    bytes: Vec<u8>,
+    source_length: usize,
+    derivation: Derivation,
+    required: f32,
+) -> Option<RecoveredOutput> {
+    let inspection = inspect(artifact.format, &bytes);
+    (inspection.score >= required).then(|| {
+        RecoveredOutput::new(
+            artifact.offset,
+            source_length as u64,
+            artifact.format,
+            derivation,
+            bytes,
+            inspection,
+        )
+    })
+}
+
+fn chunk_names(chunk_types: &[[u8; 4]]) -> Vec<String> {
+    chunk_types
+        .iter()
+        .map(|chunk_type| String::from_utf8_lossy(chunk_type).into_owned())
+        .collect()
+}
+
+#[derive(Debug, Default)]
+struct AssessedArtifact {
+    score: Option<f32>,
+    dimensions: Option<(u32, u32)>,
+    rejection: Option<Rejection>,
+    outputs: Vec<RecoveredOutput>,
+}
+
+fn extraction_bytes(artifact: &Artifact) -> u64 {
+    artifact.length.min(MAX_EXTRACTION_BYTES as u64)
+}
+
+fn extraction_batches(artifacts: &[Artifact]) -> Vec<&[Artifact]> {
+    let mut batches = Vec::new();
+    let mut start = 0;
+    let mut pending = 0_u64;
+    for (index, artifact) in artifacts.iter().enumerate() {
+        let length = extraction_bytes(artifact);
+        if index > start && pending + length > EXTRACTION_BATCH_BYTES {
+            batches.push(&artifacts[start..index]);
+            start = index;
+            pending = 0;
+        }
+        pending += length;
+    }
+    if start < artifacts.len() {
+        batches.push(&artifacts[start..]);
+    }
+    batches
+}
+
+fn assess_artifact(artifact: &Artifact, bytes: Vec<u8>) -> AssessedArtifact {
+    let inspection = inspect(artifact.format, &bytes);
+    let score = inspection.score;
+    let dimensions = inspection.dimensions;
+    let derived = derived_output(artifact, &bytes, &inspection);
+    let rejection = if score > 0.0 {
+        None
+    } else {
+        rejection_for(artifact, &bytes)
+    };
+    let mut outputs = Vec::with_capacity(2);
+    if score > 0.0 {
+        outputs.push(RecoveredOutput::new(
+            artifact.offset,
+            artifact.length,
+            artifact.format,
+            Derivation::Carved,
+            bytes,
+            inspection,
+        ));
+    }
+    outputs.extend(derived);
+    AssessedArtifact {
+        score: Some(score),
+        dimensions,
+        rejection,
+        outputs,
+    }
+}
+
+fn import_mapfile(
+    mapfile: &Path,
+    bad_map: &mut BadSectorMap,
+    audit: &mut AuditLog,
+) -> Result<(), ArgosError> {
+    let blocks = std::fs::read_to_string(mapfile)
+        .ok()
+        .and_then(|text| ddrescue::parse(&text));
+    let status = match &blocks {
+        Some(blocks) => {
+            for (offset, length) in ddrescue::unreadable_ranges(blocks) {
+                bad_map.record(offset, length);
+            }
+            Status::Ok
+        }
+        None => {
+            tracing::warn!("ddrescue mapfile could not be parsed");
+            Status::Error
+        }
+    };
+    audit.append(AuditEntry::new(
+        Operation::Read,
+        mapfile.to_string_lossy().into_owned(),
+        None,
+        None,
+        status,
+    ))
+}
+
+fn hash_window(device: &SourceDevice, offset: u64, length: u64) -> Option<[u8; 32]> {
+    if length == 0 {
+        return Some(crate::custody::hash(&[]));
+    }
+    let sector = device.sector_size() as u64;
+    let start = offset - offset % sector;
+    let span = usize::try_from((offset - start + length).next_multiple_of(sector)).ok()?;
+    let mut buf = AlignedBuf::with_capacity(span, device.sector_size()).ok()?;
+    buf.set_len(span);
+    let n = device.read_range(buf.as_mut_slice(), start).ok()?;
+    let skip = (offset - start) as usize;
+    let end = skip + usize::try_from(length).ok()?;
+    if n < end {
+        return None;
     }
+    Some(crate::custody::hash(buf.as_slice().get(skip..end)?))
+}
+
+fn fingerprint(
+    device: &SourceDevice,
+    size: u64,
+    identity: DeviceIdentity,
+) -> Option<DeviceFingerprint> {
+    let window = FINGERPRINT_WINDOW.min(size);
+    let head = hash_window(device, 0, window)?;
+    let tail = hash_window(device, size - window, window)?;
+    Some(DeviceFingerprint {
+        identity,
+        size_bytes: size,
+        head_sha256: hex::encode(head),
+        tail_sha256: hex::encode(tail),
+        verified_at_end: false,
+    })
+}
+
+fn verify_fingerprint(
+    device: &SourceDevice,
+    source_path: &Path,
+    start: &DeviceFingerprint,
+) -> bool {
+    let Ok(size) = device.size() else {
+        return false;
+    };
+    fingerprint(device, size, devices::identity(source_path)).is_some_and(|end| {
+        end.identity == start.identity
+            && end.size_bytes == start.size_bytes
+            && end.head_sha256 == start.head_sha256
+            && end.tail_sha256 == start.tail_sha256
+    })
+}
+
+fn probe_volumes(
+    device: &SourceDevice,
+    size: u64,
+    partition_table: Option<&PartitionTable>,
+) -> Vec<Volume> {
+    partition::volume_starts(partition_table)
+        .into_iter()
+        .filter_map(|(offset, partition)| {
+            let head = read_head(device, size, offset, volume::PROBE_BYTES)?;
+            let filesystem = volume::probe(&head)?;
+            let logical_volumes = lvm::metadata_area(&head)
+                .map(|area| {
+                    let text: Option<Vec<u8>> = area
+                        .ranges
+                        .iter()
+                        .map(|&(start, length)| {
+                            read_head(device, size, offset.checked_add(start)?, length)
+                        })
+                        .collect::<Option<Vec<_>>>()
+                        .map(|parts| parts.concat());
+                    text.map(|text| lvm::logical_volumes(&area, &text, offset))
+                        .unwrap_or_default()
+                })
+                .unwrap_or_default();
+            Some(Volume {
+                offset,
+                partition,
+                filesystem,
+                luks: volume::luks_header(&head),
+                logical_volumes,
+            })
+        })
+        .collect()
 }
 
 fn run_with_callbacks(
@@ -146,7 +618,10 @@ fn run_with_callbacks(
     forced_device_class: Option<DeviceClass>,
     mut on_progress: impl FnMut(ProgressEvent),
     mut on_artifact: impl FnMut(ArtifactEvent),
+    mut on_warning: impl FnMut(SessionWarningEvent),
 ) -> Result<(), ArgosError> {
+    let started_at = SystemTime::now();
+    let started = Instant::now();
     let device = SourceDevice::open(source_path)?;
     let size = device.size()?;
     let sector_size = device.sector_size();
@@ -163,13 +638,28 @@ fn run_with_callbacks(
         Status::Ok,
     ))?;
 
+    if let Some(message) = encryption::assess(&device, size)?.warning() {
+        tracing::warn!(encryption_warning = true, session_id = session.id);
+        on_warning(SessionWarningEvent {
+            session_id: session.id,
+            message,
+        });
+    }
+
+    let mut fingerprint = fingerprint(&device, size, devices::identity(source_path));
+
+    let mut manifest = Manifest::create(&output_path.join("manifest.jsonl"))?;
+
     let extraction_file = std::fs::File::open(source_path)?;
     let mut bad_map = BadSectorMap::new();
+    if let Some(mapfile) = ddrescue::sibling_mapfile(source_path) {
+        import_mapfile(&mapfile, &mut bad_map, &mut audit)?;
+    }
 
     let device_class =
         forced_device_class.unwrap_or_else(|| crate::io::detect_device_class(source_path));
 
-    let (all_candidates, bytes_scanned) = match device_class {
+    let scan = match device_class {
         DeviceClass::Ssd => scan_ssd(
             &device,
             size,
@@ -184,88 +674,243 @@ fn run_with_callbacks(
         }
     };
 
+    let partition_table = read_head(&device, size, 0, partition::PROBE_BYTES)
+        .and_then(|head| partition::parse(&head));
+    let volumes = probe_volumes(&device, size, partition_table.as_ref());
+
     let bad_path = output_path.join("bad_sectors.csv");
     bad_map.write_to(&bad_path)?;
+    let bad_ranges = bad_map.coalesced();
+    std::fs::write(
+        output_path.join("bad_sectors.map"),
+        ddrescue::render(&bad_ranges, size),
+    )?;
+    let bad_sectors = BadSectorSummary {
+        ranges: bad_ranges.len() as u64,
+        bytes: bad_ranges.iter().map(|(_, length)| length).sum(),
+        salvaged_sectors: bad_map.salvaged_sectors(),
+    };
 
+    let ScanOutcome {
+        candidates: all_candidates,
+        bytes_scanned,
+        nested_suppressed,
+        zero_bytes_skipped,
+        cluster_size,
+        digest: source_digest,
+    } = scan;
     let artifacts = reassemble_ssd(all_candidates);
     let candidates_found = artifacts.len() as u64;
+    let mut candidate_summary = CandidateSummary::default();
+    for artifact in &artifacts {
+        candidate_summary.record(artifact.format, artifact.length);
+    }
 
-    let validated: Vec<_> = artifacts
-        .par_iter()
-        .filter_map(|artifact| {
-            if session.cancel.load(Ordering::Relaxed) {
-                return None;
-            }
-            let bytes =
+    let output_space = sink
+        .available_bytes()
+        .ok()
+        .map(|available_bytes| OutputSpace {
+            required_bytes: artifacts.iter().map(extraction_bytes).sum(),
+            available_bytes,
+        });
+    if output_space.is_some_and(|space| space.required_bytes > space.available_bytes) {
+        tracing::warn!(?output_space, "output filesystem may run out of space");
+    }
+    let mut output_full = false;
+
+    let mut records = Vec::with_capacity(artifacts.len());
+    let mut file_objects = Vec::with_capacity(artifacts.len());
+    let mut gallery_entries = Vec::with_capacity(artifacts.len());
+    let mut recovered = 0_u64;
+    let mut crc_repaired = 0_u64;
+    let extraction_progress = |extracted: u64, recovered: u64| ProgressEvent {
+        session_id: session.id,
+        phase: ProgressPhase::Extracting,
+        bytes_scanned,
+        total_bytes: size,
+        candidates_found,
+        candidates_extracted: extracted,
+        artifacts_recovered: recovered,
+    };
+    on_progress(extraction_progress(0, 0));
+    for batch in extraction_batches(&artifacts) {
+        let assessed: Vec<AssessedArtifact> = batch
+            .par_iter()
+            .map(|artifact| {
+                if output_full || session.cancel.load(Ordering::Relaxed) {
+                    return AssessedArtifact::default();
+                }
                 read_artifact_bytes(&extraction_file, size, artifact.offset, artifact.length)
                     .ok()
-                    .flatten()?;
+                    .flatten()
+                    .map_or_else(AssessedArtifact::default, |bytes| {
+                        assess_artifact(artifact, bytes)
+                    })
+            })
+            .collect();
 
-            let score = match artifact.format {
-                ImageFormat::Jpeg => validate::jpeg::validate(&bytes).ok()?,
-                ImageFormat::Png => validate::png::validate(&bytes).ok()?,
-            };
+        for (artifact, assessed) in batch.iter().zip(assessed) {
+            let mut names = Vec::with_capacity(assessed.outputs.len());
+            for output in assessed.outputs {
+                if output_full || session.cancel.load(Ordering::Relaxed) {
+                    break;
+                }
+
+                let name = output.file_name();
+                match sink.write_file(&name, &output.bytes) {
+                    Err(ArgosError::Io(ref e)) if e.kind() == std::io::ErrorKind::StorageFull => {
+                        audit.append(AuditEntry::new(
+                            Operation::Recover,
+                            source_path.to_string_lossy().into_owned(),
+                            Some(name),
+                            Some((output.offset, output.length)),
+                            Status::Error,
+                        ))?;
+                        output_full = true;
+                        break;
+                    }
+                    written => written?,
+                }
 
-            if score > 0.0 {
-                let hash = crate::custody::hash(&bytes);
-                Some((artifact, score, bytes, hash))
-            } else {
-                None
+                let unreadable_bytes =
+                    unreadable_overlap(&bad_ranges, output.offset, output.length);
+                file_objects.push(output.file_object(name.clone()));
+                let entry = output.manifest_entry(
+                    name.clone(),
+                    source_path.to_string_lossy().into_owned(),
+                    unreadable_bytes,
+                );
+                manifest.append(&entry)?;
+                gallery_entries.push(entry);
+                audit.append(AuditEntry::new(
+                    Operation::Recover,
+                    source_path.to_string_lossy().into_owned(),
+                    Some(name.clone()),
+                    Some((output.offset, output.length)),
+                    if unreadable_bytes > 0 {
+                        Status::Partial
+                    } else {
+                        output.derivation.status()
+                    },
+                ))?;
+                names.push(name);
+                recovered += 1;
+                crc_repaired += output.chunks_repaired;
+
+                on_artifact(ArtifactEvent {
+                    session_id: session.id,
+                    offset: output.offset,
+                    length: output.length,
+                    format: format!("{:?}", output.format),
+                    score: output.score,
+                });
             }
-        })
-        .collect();
 
-    for (recovered, (artifact, score, bytes, hash)) in (1_u64..).zip(validated) {
-        if session.cancel.load(Ordering::Relaxed) {
-            break;
+            records.push(CandidateRecord {
+                offset: artifact.offset,
+                length: artifact.length,
+                format: artifact.format,
+                score: assessed.score,
+                width: assessed.dimensions.map(|(w, _)| w),
+                height: assessed.dimensions.map(|(_, h)| h),
+                partition: partition_table
+                    .as_ref()
+                    .and_then(|table| table.containing(artifact.offset))
+                    .map(|p| p.index),
+                outputs: names,
+                rejection: assessed.rejection,
+            });
+            on_progress(extraction_progress(records.len() as u64, recovered));
         }
+    }
 
-        let name = format!(
-            "{}_{}_{}_{:.2}.{}",
-            hex::encode(&hash[..4]),
-            artifact.offset,
-            artifact.length,
-            score,
-            extension_for(artifact.format),
-        );
-        let mut writer = sink.create_file(&name)?;
-        std::io::Write::write_all(&mut writer, &bytes)?;
-        drop(writer);
-
-        audit.append(AuditEntry::new(
-            Operation::Recover,
-            source_path.to_string_lossy().into_owned(),
-            Some(name.clone()),
-            Some((artifact.offset, artifact.length)),
-            Status::Ok,
-        ))?;
-
-        on_artifact(ArtifactEvent {
-            session_id: session.id,
-            offset: artifact.offset,
-            length: artifact.length,
-            format: format!("{:?}", artifact.format),
-            score,
-        });
-        on_progress(ProgressEvent {
-            session_id: session.id,
-            bytes_scanned,
-            candidates_found,
-            artifacts_recovered: recovered,
-        });
+    let source_unchanged = fingerprint
+        .as_ref()
+        .is_none_or(|start| verify_fingerprint(&device, source_path, start));
+    if let Some(fingerprint) = fingerprint.as_mut() {
+        fingerprint.verified_at_end = source_unchanged;
     }
 
+    let report = ScanReport {
+        schema_version: SCHEMA_VERSION,
+        source_id: source_path.to_string_lossy().into_owned(),
+        device: DeviceSummary {
+            size_bytes: size,
+            sector_size: sector_size as u64,
+            device_class,
+        },
+        fingerprint,
+        source_digest,
+        timing: ScanTiming {
+            started_at: unix_seconds(started_at),
+            finished_at: unix_seconds(SystemTime::now()),
+            duration_ms: started.elapsed().as_millis() as u64,
+        },
+        bytes_scanned,
+        nested_suppressed,
+        zero_bytes_skipped,
+        crc_repaired,
+        cluster_size,
+        cancelled: session.cancel.load(Ordering::Relaxed),
+        candidate_summary,
+        output_space,
+        output_full,
+        partition_table,
+        volumes,
+        bad_sectors,
+        candidates: records,
+    };
+    report.write_json(&output_path.join("scan_report.json"))?;
+    report.write_csv(&output_path.join("scan_report.csv"))?;
+
+    let dfxml = DfxmlDocument {
+        source: DfxmlSource {
+            image_filename: report.source_id,
+            image_size: size,
+            sector_size: sector_size as u64,
+        },
+        file_objects,
+    };
+    dfxml.write_to(&output_path.join("dfxml.xml"))?;
+
+    let gallery = Gallery {
+        source_id: dfxml.source.image_filename,
+        entries: gallery_entries,
+    };
+    gallery.write_to(&output_path.join("report.html"))?;
+
     audit.append(AuditEntry::new(
         Operation::Close,
         source_path.to_string_lossy().into_owned(),
         None,
         None,
-        Status::Ok,
+        if !source_unchanged {
+            Status::Error
+        } else if report.cancelled {
+            Status::Partial
+        } else {
+            Status::Ok
+        },
     ))?;
 
+    if !source_unchanged {
+        return Err(ArgosError::SourceChanged);
+    }
+    if output_full {
+        return Err(ArgosError::OutputFull);
+    }
     Ok(())
 }
 
+struct ScanOutcome {
+    candidates: Vec<Candidate>,
+    bytes_scanned: u64,
+    nested_suppressed: u64,
+    zero_bytes_skipped: u64,
+    cluster_size: Option<ClusterEstimate>,
+    digest: Option<SourceDigest>,
+}
+
 fn scan_ssd(
     device: &SourceDevice,
     size: u64,
@@ -273,7 +918,7 @@ fn scan_ssd(
     session: &Session,
     bad_map: &mut BadSectorMap,
     on_progress: &mut impl FnMut(ProgressEvent),
-) -> Result<(Vec<Candidate>, u64), ArgosError> {
+) -> Result<ScanOutcome, ArgosError> {
     let buf = AlignedBuf::with_capacity(1024 * 1024, sector_size)?;
     let mut reader = BlockReader::new(device, buf, size);
     let mut scanner = Scanner::new()?;
@@ -281,53 +926,124 @@ fn scan_ssd(
     let mut candidates_found: u64 = 0;
     let mut all_candidates: Vec<Candidate> = Vec::new();
 
-    while let Some(block) = reader.try_next()? {
-        if session.cancel.load(Ordering::Relaxed) {
-            break;
+    let digest = std::thread::scope(|scope| -> Result<Option<StreamDigest>, ArgosError> {
+        let (blocks, received) = std::sync::mpsc::channel::<(u64, Vec<u8>)>();
+        let (spares, pool) = std::sync::mpsc::channel::<Vec<u8>>();
+        for _ in 0..DIGEST_QUEUE {
+            spares.send(Vec::new()).ok();
         }
-        bytes_scanned += block.len() as u64;
-        let found = scanner.scan_block(block)?;
-        candidates_found += found.len() as u64;
-        all_candidates.extend(found);
-        on_progress(ProgressEvent {
-            session_id: session.id,
-            bytes_scanned,
-            candidates_found,
-            artifacts_recovered: 0,
+        let hasher = scope.spawn(move || {
+            let mut digest = StreamDigest::new();
+            for (offset, block) in received {
+                digest.update(offset, &block);
+                spares.send(block).ok();
+            }
+            digest
         });
-    }
+
+        while let Some((offset, block)) = reader.try_next()? {
+            if session.cancel.load(Ordering::Relaxed) {
+                break;
+            }
+            let Ok(mut spare) = pool.recv() else {
+                break;
+            };
+            spare.clear();
+            spare.extend_from_slice(block);
+            if blocks.send((offset, spare)).is_err() {
+                break;
+            }
+            bytes_scanned += block.len() as u64;
+            scanner.skip_to(offset);
+            let found = scanner.scan_block(block)?;
+            candidates_found += found.len() as u64;
+            all_candidates.extend(found);
+            on_progress(ProgressEvent {
+                session_id: session.id,
+                phase: ProgressPhase::Scanning,
+                bytes_scanned,
+                total_bytes: size,
+                candidates_found,
+                candidates_extracted: 0,
+                artifacts_recovered: 0,
+            });
+        }
+        drop(blocks);
+        Ok(hasher.join().ok())
+    })?;
 
     for (offset, length) in reader.bad_sectors() {
         bad_map.record(*offset, *length);
     }
+    bad_map.record_salvaged(reader.salvaged_sectors());
 
-    Ok((all_candidates, bytes_scanned))
+    let complete = !session.cancel.load(Ordering::Relaxed);
+    Ok(ScanOutcome {
+        candidates: all_candidates,
+        bytes_scanned,
+        nested_suppressed: scanner.nested_suppressed(),
+        zero_bytes_skipped: scanner.zero_bytes_skipped(),
+        cluster_size: None,
+        digest: digest.filter(|_| complete).map(|d| d.finish(size)),
+    })
 }
 
 fn scan_hdd(
     data: &[u8],
-    block_size: usize,
+    sector_size: usize,
     session: &Session,
     size: u64,
     on_progress: &mut impl FnMut(ProgressEvent),
-) -> Result<(Vec<Candidate>, u64), ArgosError> {
+) -> Result<ScanOutcome, ArgosError> {
     let session_id = session.id;
-    let candidates = crate::carve::hdd::scan(data, block_size, |bytes_scanned| {
-        on_progress(ProgressEvent {
-            session_id,
-            bytes_scanned,
-            candidates_found: 0,
-            artifacts_recovered: 0,
+    let (scan, digest) = std::thread::scope(|scope| {
+        let (chunks, received) = std::sync::mpsc::sync_channel::<(u64, &[u8])>(DIGEST_QUEUE);
+        let hasher = scope.spawn(move || {
+            let mut digest = StreamDigest::new();
+            for (offset, chunk) in received {
+                digest.update(offset, chunk);
+            }
+            digest
         });
-        !session.cancel.load(Ordering::Relaxed)
-    })?;
+        let scan = crate::carve::hdd::scan(data, sector_size, |offset, chunk| {
+            chunks.send((offset, chunk)).ok();
+            on_progress(ProgressEvent {
+                session_id,
+                phase: ProgressPhase::Scanning,
+                bytes_scanned: offset + chunk.len() as u64,
+                total_bytes: size,
+                candidates_found: 0,
+                candidates_extracted: 0,
+                artifacts_recovered: 0,
+            });
+            !session.cancel.load(Ordering::Relaxed)
+        });
+        drop(chunks);
+        (scan, hasher.join().ok())
+    });
+    let HddScan {
+        candidates,
+        cluster,
+    } = scan?;
     on_progress(ProgressEvent {
         session_id,
+        phase: ProgressPhase::Scanning,
         bytes_scanned: size,
+        total_bytes: size,
         candidates_found: candidates.len() as u64,
+        candidates_extracted: 0,
         artifacts_recovered: 0,
     });
-    Ok((candidates, size))
+    Ok(ScanOutcome {
+        candidates,
+        bytes_scanned: size,
+        nested_suppressed: 0,
+        zero_bytes_skipped: 0,
+        cluster_size: cluster,
+        digest: digest
+            .filter(|_| !session.cancel.load(Ordering::Relaxed))
+            .map(|d| d.finish(size)),
+    })
 }
 
 pub fn emit_completed(
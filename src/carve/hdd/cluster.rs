@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+const CLUSTER_SIZES: [u64; 8] = [512, 1024, 2048, 4096, 8192, 16384, 32768, 65536];
+const MIN_ALIGNED_HEADERS: usize = 8;
+const MIN_ALIGNED_PERCENT: usize = 80;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClusterEstimate {
+    pub size: u64,
+    pub headers: u64,
+}
+
+pub fn estimate(header_offsets: &[u64], sector_size: u64) -> Option<ClusterEstimate> {
+    if sector_size == 0 {
+        return None;
+    }
+    let starts: Vec<u64> = header_offsets
+        .iter()
+        .copied()
+        .filter(|offset| offset % sector_size == 0)
+        .collect();
+    if starts.len() < MIN_ALIGNED_HEADERS {
+        return None;
+    }
+    CLUSTER_SIZES
+        .iter()
+        .rev()
+        .filter(|&&size| size >= sector_size && size % sector_size == 0)
+        .find_map(|&size| {
+            let aligned = starts.iter().filter(|&&offset| offset % size == 0).count();
+            (aligned * 100 >= starts.len() * MIN_ALIGNED_PERCENT).then_some(ClusterEstimate {
+                size,
+                headers: starts.len() as u64,
+            })
+        })
+}
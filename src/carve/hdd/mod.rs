@@ -1,53 +1,92 @@
-use crate::carve::Candidate;
+use crate::carve::hdd::cluster::ClusterEstimate;
 use crate::carve::hdd::pup::{Seed, run};
-use crate::carve::ssd::patterns::{PatternKind, all_patterns};
+use crate::carve::matcher::Matcher;
+use crate::carve::ssd::patterns::PatternKind;
+use crate::carve::{Candidate, ImageFormat};
 use crate::error::ArgosError;
 
+pub mod cluster;
 pub mod pup;
 pub mod sht;
 
 const SCAN_CHUNK_SIZE: usize = 64 * 1024 * 1024;
 const PUP_MAX_BLOCKS: usize = 10_000;
 
-pub fn scan(
-    data: &[u8],
-    block_size: usize,
-    mut on_progress: impl FnMut(u64) -> bool,
-) -> Result<Vec<Candidate>, ArgosError> {
-    let patterns = all_patterns();
-    let pattern_bytes: Vec<&[u8]> = patterns.iter().map(|(p, _)| *p).collect();
-    let ac = aho_corasick::AhoCorasick::new(&pattern_bytes)?;
-    let max_pattern_len = pattern_bytes.iter().map(|p| p.len()).max().unwrap_or(0);
-    let pattern_kinds: Vec<PatternKind> = patterns.iter().map(|(_, k)| *k).collect();
-    let overlap = max_pattern_len.saturating_sub(1);
-
-    let mut seeds = Vec::new();
+#[derive(Debug)]
+pub struct HddScan {
+    pub candidates: Vec<Candidate>,
+    pub cluster: Option<ClusterEstimate>,
+}
+
+pub fn scan<'a>(
+    data: &'a [u8],
+    sector_size: usize,
+    mut on_chunk: impl FnMut(u64, &'a [u8]) -> bool,
+) -> Result<HddScan, ArgosError> {
+    let matcher = Matcher::new()?;
+    let overlap = matcher.overlap();
+
+    let mut headers = Vec::new();
     let mut pos: usize = 0;
     while pos < data.len() {
         let chunk_start = pos.saturating_sub(overlap);
         let chunk_end = (pos + SCAN_CHUNK_SIZE).min(data.len());
         let chunk = &data[chunk_start..chunk_end];
-        for mat in ac.find_iter(chunk) {
-            let absolute_end = chunk_start + mat.end();
-            if absolute_end <= pos {
-                continue;
-            }
-            let absolute_start = chunk_start + mat.start();
-            let pattern_id = mat.pattern().as_usize();
-            if let PatternKind::Header(format) = pattern_kinds[pattern_id] {
-                let block_index = (absolute_start / block_size) as u64;
-                seeds.push(Seed {
-                    block_index,
-                    format,
-                });
+        for mat in matcher.find(chunk, pos - chunk_start, chunk_start as u64) {
+            if let PatternKind::Header(format) = mat.kind {
+                headers.push((mat.offset, format));
             }
         }
-        pos = chunk_end;
-        if !on_progress(pos as u64) {
+        let scanned = &data[pos..chunk_end];
+        if !on_chunk(pos as u64, scanned) {
             break;
         }
+        pos = chunk_end;
     }
 
-    let candidates = run(&seeds, data, block_size, PUP_MAX_BLOCKS);
-    Ok(candidates)
+    let offsets: Vec<u64> = headers.iter().map(|&(offset, _)| offset).collect();
+    let cluster = cluster::estimate(&offsets, sector_size as u64);
+    let block_size = cluster.map_or(sector_size, |c| c.size as usize);
+    let (on_grid, off_grid): (Vec<_>, Vec<_>) = headers
+        .into_iter()
+        .partition(|&(offset, _)| cluster.is_none() || offset % block_size as u64 == 0);
+    let mut candidates = run(
+        &seeds(&on_grid, block_size),
+        data,
+        block_size,
+        PUP_MAX_BLOCKS,
+    );
+    candidates.sort_unstable_by_key(|c| c.offset);
+    let stray: Vec<(u64, ImageFormat)> = off_grid
+        .into_iter()
+        .filter(|&(offset, _)| !inside_candidate(&candidates, offset))
+        .collect();
+    candidates.extend(run(
+        &seeds(&stray, sector_size),
+        data,
+        sector_size,
+        PUP_MAX_BLOCKS,
+    ));
+    Ok(HddScan {
+        candidates,
+        cluster,
+    })
+}
+
+fn seeds(headers: &[(u64, ImageFormat)], block_size: usize) -> Vec<Seed> {
+    headers
+        .iter()
+        .map(|&(offset, format)| Seed {
+            block_index: offset / block_size as u64,
+            format,
+        })
+        .collect()
+}
+
+fn inside_candidate(sorted: &[Candidate], offset: u64) -> bool {
+    let after = sorted.partition_point(|c| c.offset <= offset);
+    after > 0
+        && sorted[after - 1]
+            .length
+            .is_some_and(|length| offset < sorted[after - 1].offset.saturating_add(length))
 }
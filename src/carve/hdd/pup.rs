@@ -72,7 +72,7 @@ pub fn run(seeds: &[Seed], data: &[u8], block_size: usize, max_blocks: usize) ->
             continue;
         };
         let png_partial = match seed.format {
-            ImageFormat::Png => Some(png::PartialChunk::default()),
+            ImageFormat::Png => Some(png::PartialChunk::after_signature(&data[start..end])),
             ImageFormat::Jpeg => None,
         };
         let path = Path {
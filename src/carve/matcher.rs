@@ -0,0 +1,61 @@
+use aho_corasick::AhoCorasick;
+
+use crate::carve::ssd::patterns::{PatternKind, all_patterns};
+use crate::error::ArgosError;
+
+pub struct Matcher {
+    ac: AhoCorasick,
+    pattern_kinds: Vec<PatternKind>,
+    max_pattern_len: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    pub offset: u64,
+    pub len: usize,
+    pub kind: PatternKind,
+}
+
+impl Matcher {
+    pub fn new() -> Result<Self, ArgosError> {
+        let patterns = all_patterns();
+        let pattern_bytes: Vec<&[u8]> = patterns.iter().map(|(p, _)| *p).collect();
+        let ac = AhoCorasick::new(&pattern_bytes)?;
+        let max_pattern_len = pattern_bytes.iter().map(|p| p.len()).max().unwrap_or(0);
+        let pattern_kinds: Vec<PatternKind> = patterns.iter().map(|(_, k)| *k).collect();
+        Ok(Self {
+            ac,
+            pattern_kinds,
+            max_pattern_len,
+        })
+    }
+
+    pub fn overlap(&self) -> usize {
+        self.max_pattern_len.saturating_sub(1)
+    }
+
+    pub fn find<'a>(
+        &'a self,
+        haystack: &'a [u8],
+        carried: usize,
+        base: u64,
+    ) -> impl Iterator<Item = Match> + 'a {
+        self.ac
+            .find_iter(haystack)
+            .filter(move |mat| mat.end() > carried)
+            .map(move |mat| Match {
+                offset: base + mat.start() as u64,
+                len: mat.end() - mat.start(),
+                kind: self.pattern_kinds[mat.pattern().as_usize()],
+            })
+    }
+}
+
+impl std::fmt::Debug for Matcher {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Matcher")
+            .field("patterns", &self.pattern_kinds.len())
+            .field("max_pattern_len", &self.max_pattern_len)
+            .finish_non_exhaustive()
+    }
+}
@@ -1,4 +1,7 @@
+use serde::{Deserialize, Serialize};
+
 pub mod hdd;
+pub mod matcher;
 pub mod ssd;
 
 #[derive(Debug, Clone)]
@@ -8,13 +11,31 @@ pub struct Candidate {
     pub format: ImageFormat,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ImageFormat {
     Jpeg,
     Png,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl ImageFormat {
+    pub fn label(self) -> &'static str {
+        match self {
+            ImageFormat::Jpeg => "jpeg",
+            ImageFormat::Png => "png",
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Jpeg => "jpg",
+            ImageFormat::Png => self.label(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum DeviceClass {
     Hdd,
     Ssd,
@@ -1,93 +1,146 @@
 pub mod patterns;
 
-use aho_corasick::AhoCorasick;
-
-use crate::carve::ssd::patterns::{PatternKind, all_patterns};
+use crate::carve::matcher::Matcher;
+use crate::carve::ssd::patterns::PatternKind;
 use crate::carve::{Candidate, ImageFormat};
 use crate::error::ArgosError;
+use crate::validate::{StructureStep, jpeg, png};
 
 pub struct Scanner {
-    ac: AhoCorasick,
-    pattern_kinds: Vec<PatternKind>,
-    max_pattern_len: usize,
+    matcher: Matcher,
     overlap: Vec<u8>,
     concat_buf: Vec<u8>,
     offset_base: u64,
     open_candidates: Vec<OpenCandidate>,
+    nested_suppressed: u64,
+    zero_bytes_skipped: u64,
 }
 
 #[derive(Debug)]
 struct OpenCandidate {
     offset: u64,
     format: ImageFormat,
+    structure_end: u64,
+    walking: bool,
+}
+
+impl OpenCandidate {
+    fn advance(&mut self, buf: &[u8], base: u64) {
+        while self.walking {
+            let Some(at) = self
+                .structure_end
+                .checked_sub(base)
+                .and_then(|at| usize::try_from(at).ok())
+            else {
+                self.walking = false;
+                break;
+            };
+            let bytes = buf.get(at..).unwrap_or_default();
+            let step = match self.format {
+                ImageFormat::Jpeg => jpeg::header_segment_step(bytes),
+                ImageFormat::Png => png::chunk_step(bytes),
+            };
+            match step {
+                StructureStep::Skip(len) => self.structure_end += len,
+                StructureStep::End => self.walking = false,
+                StructureStep::NeedMore => break,
+            }
+        }
+    }
 }
 
 impl Scanner {
     pub fn new() -> Result<Self, ArgosError> {
-        let patterns = all_patterns();
-        let pattern_bytes: Vec<&[u8]> = patterns.iter().map(|(p, _)| *p).collect();
-        let ac = AhoCorasick::new(&pattern_bytes)?;
-        let max_pattern_len = pattern_bytes.iter().map(|p| p.len()).max().unwrap_or(0);
-        let pattern_kinds: Vec<PatternKind> = patterns.iter().map(|(_, k)| *k).collect();
+        let matcher = Matcher::new()?;
+        let overlap = matcher.overlap();
 
         Ok(Self {
-            ac,
-            pattern_kinds,
-            max_pattern_len,
-            overlap: Vec::with_capacity(max_pattern_len.saturating_sub(1)),
-            concat_buf: Vec::with_capacity(1024 * 1024 + max_pattern_len),
+            matcher,
+            overlap: Vec::with_capacity(overlap),
+            concat_buf: Vec::with_capacity(1024 * 1024 + overlap),
             offset_base: 0,
             open_candidates: Vec::new(),
+            nested_suppressed: 0,
+            zero_bytes_skipped: 0,
         })
     }
 
-    pub fn scan_block(&mut self, block: &[u8]) -> Result<Vec<Candidate>, ArgosError> {
-        let mut completed = Vec::new();
+    pub fn skip_to(&mut self, offset: u64) {
+        if offset > self.offset_base {
+            self.overlap.clear();
+            self.offset_base = offset;
+        }
+    }
 
-        self.concat_buf.clear();
-        self.concat_buf.extend_from_slice(&self.overlap);
-        self.concat_buf.extend_from_slice(block);
+    pub fn nested_suppressed(&self) -> u64 {
+        self.nested_suppressed
+    }
 
-        for mat in self.ac.find_iter(&self.concat_buf) {
-            let pattern_id = mat.pattern().as_usize();
-            let mat_start = mat.start();
-            let mat_end = mat.end();
-            let pattern_len = mat_end - mat_start;
+    pub fn zero_bytes_skipped(&self) -> u64 {
+        self.zero_bytes_skipped
+    }
 
-            if mat_start + pattern_len <= self.overlap.len() {
-                continue;
-            }
+    pub fn scan_block(&mut self, block: &[u8]) -> Result<Vec<Candidate>, ArgosError> {
+        let mut completed = Vec::new();
+        let overlap_keep = self.matcher.overlap();
 
-            let absolute_offset = self.offset_base - self.overlap.len() as u64 + mat_start as u64;
-            let pattern_kind = self.pattern_kinds[pattern_id];
+        let searched = if block.iter().all(|&b| b == 0) {
+            &block[..overlap_keep.min(block.len())]
+        } else {
+            block
+        };
+        self.zero_bytes_skipped += (block.len() - searched.len()) as u64;
 
-            match pattern_kind {
+        self.concat_buf.clear();
+        self.concat_buf.extend_from_slice(&self.overlap);
+        self.concat_buf.extend_from_slice(searched);
+
+        let base = self.offset_base - self.overlap.len() as u64;
+        for mat in self
+            .matcher
+            .find(&self.concat_buf, self.overlap.len(), base)
+        {
+            match mat.kind {
                 PatternKind::Header(format) => {
-                    if !self.open_candidates.iter().any(|c| c.format == format) {
-                        self.open_candidates.push(OpenCandidate {
-                            offset: absolute_offset,
-                            format,
-                        });
+                    let pos = self.open_candidates.iter().position(|c| c.format == format);
+                    if let Some(pos) = pos {
+                        let open = &mut self.open_candidates[pos];
+                        open.advance(&self.concat_buf, base);
+                        if mat.offset < open.structure_end {
+                            self.nested_suppressed += 1;
+                            continue;
+                        }
+                        self.open_candidates.remove(pos);
                     }
+                    self.open_candidates.push(OpenCandidate {
+                        offset: mat.offset,
+                        format,
+                        structure_end: mat.offset + mat.len as u64,
+                        walking: true,
+                    });
                 }
                 PatternKind::Footer(format) => {
-                    if let Some(pos) = self
-                        .open_candidates
-                        .iter()
-                        .rposition(|c| c.format == format)
+                    if let Some(pos) = self.open_candidates.iter().position(|c| c.format == format)
                     {
+                        let open = &mut self.open_candidates[pos];
+                        open.advance(&self.concat_buf, base);
+                        if mat.offset < open.structure_end {
+                            continue;
+                        }
                         let open = self.open_candidates.remove(pos);
                         completed.push(Candidate {
                             offset: open.offset,
-                            length: Some(absolute_offset + pattern_len as u64 - open.offset),
+                            length: Some(mat.offset + mat.len as u64 - open.offset),
                             format,
                         });
                     }
                 }
             }
         }
+        for open in &mut self.open_candidates {
+            open.advance(&self.concat_buf, base);
+        }
 
-        let overlap_keep = self.max_pattern_len.saturating_sub(1);
         self.overlap.clear();
         let keep = overlap_keep.min(self.concat_buf.len());
         if keep > 0 {
@@ -104,9 +157,11 @@ impl Scanner {
 impl std::fmt::Debug for Scanner {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Scanner")
-            .field("max_pattern_len", &self.max_pattern_len)
+            .field("matcher", &self.matcher)
             .field("offset_base", &self.offset_base)
             .field("open_count", &self.open_candidates.len())
+            .field("nested_suppressed", &self.nested_suppressed)
+            .field("zero_bytes_skipped", &self.zero_bytes_skipped)
             .finish_non_exhaustive()
     }
 }
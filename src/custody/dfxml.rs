@@ -0,0 +1,116 @@
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::custody::escape_markup;
+use crate::custody::manifest::RecoveryMethod;
+use crate::error::ArgosError;
+
+const DFXML_NAMESPACE: &str = "http://www.forensicswiki.org/wiki/Category:Digital_Forensics_XML";
+const DC_NAMESPACE: &str = "http://purl.org/dc/elements/1.1/";
+const ARGOS_NAMESPACE: &str = "https://github.com/Brevex/Argos/dfxml";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DfxmlSource {
+    pub image_filename: String,
+    pub image_size: u64,
+    pub sector_size: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRun {
+    pub file_offset: u64,
+    pub img_offset: u64,
+    pub len: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileObject {
+    pub filename: String,
+    pub filesize: u64,
+    pub byte_runs: Vec<ByteRun>,
+    pub md5: [u8; 16],
+    pub sha1: [u8; 20],
+    pub sha256: [u8; 32],
+    pub carve_method: RecoveryMethod,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DfxmlDocument {
+    pub source: DfxmlSource,
+    pub file_objects: Vec<FileObject>,
+}
+
+impl DfxmlDocument {
+    pub fn render(&self) -> String {
+        let mut out = String::with_capacity(512 + 640 * self.file_objects.len());
+        out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            out,
+            "<dfxml xmlns=\"{DFXML_NAMESPACE}\" xmlns:dc=\"{DC_NAMESPACE}\" xmlns:argos=\"{ARGOS_NAMESPACE}\" version=\"1.0\">"
+        );
+        out.push_str("  <metadata>\n    <dc:type>Carve Report</dc:type>\n  </metadata>\n");
+        let _ = writeln!(
+            out,
+            "  <creator version=\"1.0\">\n    <program>{}</program>\n    <version>{}</version>\n  </creator>",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION"),
+        );
+        let _ = writeln!(
+            out,
+            "  <source>\n    <image_filename>{}</image_filename>\n    <image_size>{}</image_size>\n    <sectorsize>{}</sectorsize>\n  </source>",
+            escape_markup(&self.source.image_filename),
+            self.source.image_size,
+            self.source.sector_size,
+        );
+        for object in &self.file_objects {
+            render_file_object(&mut out, object);
+        }
+        out.push_str("</dfxml>\n");
+        out
+    }
+
+    pub fn write_to(&self, path: &Path) -> Result<(), ArgosError> {
+        std::fs::write(path, self.render())?;
+        Ok(())
+    }
+}
+
+fn render_file_object(out: &mut String, object: &FileObject) {
+    out.push_str("  <fileobject>\n");
+    let _ = writeln!(
+        out,
+        "    <filename>{}</filename>",
+        escape_markup(&object.filename)
+    );
+    let _ = writeln!(out, "    <filesize>{}</filesize>", object.filesize);
+    out.push_str("    <byte_runs>\n");
+    for run in &object.byte_runs {
+        let _ = writeln!(
+            out,
+            "      <byte_run offset=\"{}\" img_offset=\"{}\" len=\"{}\"/>",
+            run.file_offset, run.img_offset, run.len,
+        );
+    }
+    out.push_str("    </byte_runs>\n");
+    let _ = writeln!(
+        out,
+        "    <hashdigest type=\"md5\">{}</hashdigest>",
+        hex::encode(object.md5)
+    );
+    let _ = writeln!(
+        out,
+        "    <hashdigest type=\"sha1\">{}</hashdigest>",
+        hex::encode(object.sha1)
+    );
+    let _ = writeln!(
+        out,
+        "    <hashdigest type=\"sha256\">{}</hashdigest>",
+        hex::encode(object.sha256)
+    );
+    let _ = writeln!(
+        out,
+        "    <argos:carve_method>{}</argos:carve_method>",
+        object.carve_method
+    );
+    out.push_str("  </fileobject>\n");
+}
@@ -0,0 +1,108 @@
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::custody::escape_markup;
+use crate::custody::manifest::{ManifestEntry, RecoveryMethod};
+use crate::error::ArgosError;
+
+const THUMBNAIL_EDGE: u32 = 256;
+
+const STYLE: &str = "body{font-family:system-ui,sans-serif;margin:24px;background:#111;color:#eee}\
+h1{font-size:20px}\
+.grid{display:grid;grid-template-columns:repeat(auto-fill,minmax(272px,1fr));gap:16px}\
+figure{margin:0;padding:8px;background:#1c1c1c;border-radius:6px}\
+figure img{display:block;max-width:256px;max-height:256px;margin:0 auto;object-fit:contain}\
+figcaption{font-size:12px;line-height:1.5;word-break:break-all}\
+.badge{display:inline-block;padding:0 6px;border-radius:3px;font-weight:600}\
+.partial{background:#b45309}.repaired{background:#1d4ed8}.damaged{background:#b91c1c}.icc-incomplete{background:#6d28d9}";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gallery {
+    pub source_id: String,
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl Gallery {
+    pub fn render(&self) -> String {
+        let mut out = String::with_capacity(1024 + 512 * self.entries.len());
+        out.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+        out.push_str("<meta http-equiv=\"Content-Security-Policy\" content=\"default-src 'none'; img-src 'self' file:; style-src 'unsafe-inline'\">\n");
+        let _ = writeln!(
+            out,
+            "<title>Recovered images: {}</title>\n<style>{STYLE}</style>\n</head>\n<body>",
+            escape_markup(&self.source_id)
+        );
+        let _ = writeln!(
+            out,
+            "<h1>{} recovered files from {}</h1>\n<div class=\"grid\">",
+            self.entries.len(),
+            escape_markup(&self.source_id)
+        );
+        for entry in &self.entries {
+            render_entry(&mut out, entry);
+        }
+        out.push_str("</div>\n</body>\n</html>\n");
+        out
+    }
+
+    pub fn write_to(&self, path: &Path) -> Result<(), ArgosError> {
+        std::fs::write(path, self.render())?;
+        Ok(())
+    }
+}
+
+fn render_entry(out: &mut String, entry: &ManifestEntry) {
+    let file = escape_markup(&entry.file);
+    let length: u64 = entry.fragments.iter().map(|f| f.length).sum();
+    let _ = writeln!(
+        out,
+        "<figure><a href=\"{file}\"><img src=\"{file}\" loading=\"lazy\" width=\"{THUMBNAIL_EDGE}\" alt=\"{file}\"></a>"
+    );
+    out.push_str("<figcaption>");
+    for badge in badges(entry) {
+        let _ = write!(out, "<span class=\"badge {badge}\">{badge}</span> ");
+    }
+    let _ = write!(
+        out,
+        "{} &middot; offset {} &middot; {length} bytes &middot; ",
+        entry.format.label().to_ascii_uppercase(),
+        entry.offset,
+    );
+    match (entry.width, entry.height) {
+        (Some(width), Some(height)) => {
+            let _ = write!(out, "{width}&times;{height} &middot; ");
+        }
+        _ => out.push_str("unknown size &middot; "),
+    }
+    if let Some(frames) = entry.frames {
+        let _ = write!(out, "{frames} frames");
+        if let Some(duration_ms) = entry.duration_ms {
+            let _ = write!(out, " / {:.1} s", duration_ms as f64 / 1000.0);
+        }
+        out.push_str(" &middot; ");
+    }
+    if let Some(quality) = entry.quality {
+        let _ = write!(out, "quality ~{quality} &middot; ");
+    }
+    let _ = writeln!(
+        out,
+        "confidence {:.2}<br>{file}</figcaption></figure>",
+        entry.confidence
+    );
+}
+
+fn badges(entry: &ManifestEntry) -> Vec<&'static str> {
+    let mut badges = Vec::with_capacity(3);
+    match entry.method {
+        RecoveryMethod::Carved => {}
+        RecoveryMethod::Truncated => badges.push("partial"),
+        RecoveryMethod::CrcRepaired => badges.push("repaired"),
+    }
+    if entry.unreadable_bytes > 0 {
+        badges.push("damaged");
+    }
+    if entry.icc_profile_intact == Some(false) {
+        badges.push("icc-incomplete");
+    }
+    badges
+}
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+
+use crate::carve::ImageFormat;
+use crate::error::ArgosError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RecoveryMethod {
+    Carved,
+    Truncated,
+    CrcRepaired,
+}
+
+impl fmt::Display for RecoveryMethod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.serialize(f)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Fragment {
+    pub offset: u64,
+    pub length: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub file: String,
+    pub source_id: String,
+    pub offset: u64,
+    pub fragments: Vec<Fragment>,
+    pub format: ImageFormat,
+    pub method: RecoveryMethod,
+    pub confidence: f32,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    #[serde(default)]
+    pub frames: Option<u32>,
+    #[serde(default)]
+    pub duration_ms: Option<u64>,
+    #[serde(default)]
+    pub icc_profile_intact: Option<bool>,
+    #[serde(default)]
+    pub icc_missing_segments: Vec<u8>,
+    #[serde(default)]
+    pub quality: Option<u8>,
+    pub sha256: String,
+    #[serde(default)]
+    pub unreadable_bytes: u64,
+    #[serde(default)]
+    pub dropped_chunks: Vec<String>,
+    #[serde(default)]
+    pub chunks_repaired: u64,
+    pub recovered_at: u64,
+}
+
+pub struct Manifest {
+    file: std::fs::File,
+}
+
+impl Manifest {
+    pub fn create(path: &Path) -> Result<Self, ArgosError> {
+        let file = std::fs::OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)?;
+        Ok(Self { file })
+    }
+
+    pub fn append(&mut self, entry: &ManifestEntry) -> Result<(), ArgosError> {
+        let mut buf = Vec::with_capacity(384);
+        serde_json::to_writer(&mut buf, entry)?;
+        buf.push(b'\n');
+        self.file.write_all(&buf)?;
+        self.file.flush()?;
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Manifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Manifest").finish_non_exhaustive()
+    }
+}
@@ -1,16 +1,120 @@
+use md5::Md5;
 use serde::Serialize;
+use sha1::Sha1;
 use sha2::{Digest, Sha256};
 use std::io::Write;
 use std::path::Path;
 
 use crate::error::ArgosError;
 
+pub mod dfxml;
+pub mod gallery;
+pub mod manifest;
+pub mod report;
+
+pub fn unix_seconds(time: std::time::SystemTime) -> u64 {
+    time.duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
 pub fn hash(data: &[u8]) -> [u8; 32] {
     let mut hasher = Sha256::new();
     hasher.update(data);
     hasher.finalize().into()
 }
 
+pub fn escape_markup(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c if c.is_control() && !matches!(c, '\t' | '\n' | '\r') => {
+                escaped.push(char::REPLACEMENT_CHARACTER)
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+pub fn md5(data: &[u8]) -> [u8; 16] {
+    Md5::digest(data).into()
+}
+
+pub fn sha1(data: &[u8]) -> [u8; 20] {
+    Sha1::digest(data).into()
+}
+
+const ZERO_FILL_CHUNK: usize = 64 * 1024;
+
+pub struct StreamDigest {
+    md5: Md5,
+    sha256: Sha256,
+    position: u64,
+    zero_filled_bytes: u64,
+}
+
+impl Default for StreamDigest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StreamDigest {
+    pub fn new() -> Self {
+        Self {
+            md5: Md5::new(),
+            sha256: Sha256::new(),
+            position: 0,
+            zero_filled_bytes: 0,
+        }
+    }
+
+    pub fn update(&mut self, offset: u64, bytes: &[u8]) {
+        if offset < self.position {
+            return;
+        }
+        self.zero_fill_to(offset);
+        self.md5.update(bytes);
+        self.sha256.update(bytes);
+        self.position += bytes.len() as u64;
+    }
+
+    pub fn finish(mut self, size: u64) -> report::SourceDigest {
+        self.zero_fill_to(size);
+        report::SourceDigest {
+            md5: hex::encode(self.md5.finalize()),
+            sha256: hex::encode(self.sha256.finalize()),
+            zero_filled_bytes: self.zero_filled_bytes,
+        }
+    }
+
+    fn zero_fill_to(&mut self, offset: u64) {
+        let zeros = [0u8; ZERO_FILL_CHUNK];
+        while self.position < offset {
+            let n = (offset - self.position).min(ZERO_FILL_CHUNK as u64) as usize;
+            self.md5.update(&zeros[..n]);
+            self.sha256.update(&zeros[..n]);
+            self.position += n as u64;
+            self.zero_filled_bytes += n as u64;
+        }
+    }
+}
+
+impl std::fmt::Debug for StreamDigest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamDigest")
+            .field("position", &self.position)
+            .field("zero_filled_bytes", &self.zero_filled_bytes)
+            .finish_non_exhaustive()
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Operation {
@@ -49,10 +153,7 @@ impl AuditEntry {
         status: Status,
     ) -> Self {
         Self {
-            timestamp: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs(),
+            timestamp: unix_seconds(std::time::SystemTime::now()),
             operation,
             source_id,
             output_id,
@@ -100,6 +201,7 @@ impl std::fmt::Debug for AuditLog {
 
 pub struct BadSectorMap {
     entries: Vec<(u64, u64)>,
+    salvaged_sectors: u64,
 }
 
 impl Default for BadSectorMap {
@@ -112,6 +214,7 @@ impl BadSectorMap {
     pub fn new() -> Self {
         Self {
             entries: Vec::new(),
+            salvaged_sectors: 0,
         }
     }
 
@@ -119,10 +222,33 @@ impl BadSectorMap {
         self.entries.push((offset, length));
     }
 
+    pub fn record_salvaged(&mut self, sectors: u64) {
+        self.salvaged_sectors += sectors;
+    }
+
+    pub fn salvaged_sectors(&self) -> u64 {
+        self.salvaged_sectors
+    }
+
     pub fn entries(&self) -> &[(u64, u64)] {
         &self.entries
     }
 
+    pub fn coalesced(&self) -> Vec<(u64, u64)> {
+        let mut sorted = self.entries.clone();
+        sorted.sort_unstable();
+        let mut ranges: Vec<(u64, u64)> = Vec::with_capacity(sorted.len());
+        for (offset, length) in sorted {
+            match ranges.last_mut() {
+                Some((start, len)) if offset <= start.saturating_add(*len) => {
+                    *len = (*len).max(offset.saturating_add(length) - *start);
+                }
+                _ => ranges.push((offset, length)),
+            }
+        }
+        ranges
+    }
+
     pub fn write_to(&self, path: &Path) -> Result<(), ArgosError> {
         let mut file = std::fs::OpenOptions::new()
             .create(true)
@@ -136,10 +262,21 @@ impl BadSectorMap {
     }
 }
 
+pub fn unreadable_overlap(coalesced: &[(u64, u64)], offset: u64, length: u64) -> u64 {
+    let end = offset.saturating_add(length);
+    let first = coalesced.partition_point(|&(start, len)| start.saturating_add(len) <= offset);
+    coalesced[first..]
+        .iter()
+        .take_while(|&&(start, _)| start < end)
+        .map(|&(start, len)| start.saturating_add(len).min(end) - start.max(offset))
+        .sum()
+}
+
 impl std::fmt::Debug for BadSectorMap {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("BadSectorMap")
             .field("count", &self.entries.len())
+            .field("salvaged_sectors", &self.salvaged_sectors)
             .finish_non_exhaustive()
     }
 }
@@ -0,0 +1,189 @@
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::Path;
+
+use crate::carve::hdd::cluster::ClusterEstimate;
+use crate::carve::{DeviceClass, ImageFormat};
+use crate::error::{ArgosError, ValidationKind};
+use crate::io::partition::PartitionTable;
+use crate::io::volume::Volume;
+
+pub const SCHEMA_VERSION: u32 = 1;
+const SMALL_CANDIDATE_BYTES: u64 = 100 * 1024;
+const MEDIUM_CANDIDATE_BYTES: u64 = 1024 * 1024;
+const LARGE_CANDIDATE_BYTES: u64 = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanReport {
+    pub schema_version: u32,
+    pub source_id: String,
+    pub device: DeviceSummary,
+    #[serde(default)]
+    pub fingerprint: Option<DeviceFingerprint>,
+    #[serde(default)]
+    pub source_digest: Option<SourceDigest>,
+    pub timing: ScanTiming,
+    pub bytes_scanned: u64,
+    #[serde(default)]
+    pub nested_suppressed: u64,
+    #[serde(default)]
+    pub zero_bytes_skipped: u64,
+    #[serde(default)]
+    pub crc_repaired: u64,
+    #[serde(default)]
+    pub cluster_size: Option<ClusterEstimate>,
+    pub cancelled: bool,
+    #[serde(default)]
+    pub candidate_summary: CandidateSummary,
+    #[serde(default)]
+    pub output_space: Option<OutputSpace>,
+    #[serde(default)]
+    pub output_full: bool,
+    #[serde(default)]
+    pub partition_table: Option<PartitionTable>,
+    #[serde(default)]
+    pub volumes: Vec<Volume>,
+    #[serde(default)]
+    pub bad_sectors: BadSectorSummary,
+    pub candidates: Vec<CandidateRecord>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceSummary {
+    pub size_bytes: u64,
+    pub sector_size: u64,
+    pub device_class: DeviceClass,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceIdentity {
+    pub model: Option<String>,
+    pub serial: Option<String>,
+    pub firmware: Option<String>,
+    pub wwn: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeviceFingerprint {
+    #[serde(flatten)]
+    pub identity: DeviceIdentity,
+    pub size_bytes: u64,
+    pub head_sha256: String,
+    pub tail_sha256: String,
+    pub verified_at_end: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceDigest {
+    pub md5: String,
+    pub sha256: String,
+    pub zero_filled_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CandidateSummary {
+    pub jpeg: u64,
+    pub png: u64,
+    pub up_to_100_kib: u64,
+    pub up_to_1_mib: u64,
+    pub up_to_10_mib: u64,
+    pub over_10_mib: u64,
+}
+
+impl CandidateSummary {
+    pub fn record(&mut self, format: ImageFormat, length: u64) {
+        match format {
+            ImageFormat::Jpeg => self.jpeg += 1,
+            ImageFormat::Png => self.png += 1,
+        }
+        let bucket = if length <= SMALL_CANDIDATE_BYTES {
+            &mut self.up_to_100_kib
+        } else if length <= MEDIUM_CANDIDATE_BYTES {
+            &mut self.up_to_1_mib
+        } else if length <= LARGE_CANDIDATE_BYTES {
+            &mut self.up_to_10_mib
+        } else {
+            &mut self.over_10_mib
+        };
+        *bucket += 1;
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputSpace {
+    pub required_bytes: u64,
+    pub available_bytes: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanTiming {
+    pub started_at: u64,
+    pub finished_at: u64,
+    pub duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BadSectorSummary {
+    pub ranges: u64,
+    pub bytes: u64,
+    #[serde(default)]
+    pub salvaged_sectors: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CandidateRecord {
+    pub offset: u64,
+    pub length: u64,
+    pub format: ImageFormat,
+    pub score: Option<f32>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    #[serde(default)]
+    pub partition: Option<u32>,
+    pub outputs: Vec<String>,
+    #[serde(default)]
+    pub rejection: Option<Rejection>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rejection {
+    pub kind: ValidationKind,
+    pub offset: u64,
+}
+
+impl ScanReport {
+    pub fn write_json(&self, path: &Path) -> Result<(), ArgosError> {
+        let mut buf = serde_json::to_vec_pretty(self)?;
+        buf.push(b'\n');
+        std::fs::write(path, buf)?;
+        Ok(())
+    }
+
+    pub fn write_csv(&self, path: &Path) -> Result<(), ArgosError> {
+        let mut buf = Vec::with_capacity(64 * (self.candidates.len() + 1));
+        writeln!(
+            buf,
+            "offset,length,format,score,width,height,partition,outputs"
+        )?;
+        for record in &self.candidates {
+            writeln!(
+                buf,
+                "{},{},{},{},{},{},{},{}",
+                record.offset,
+                record.length,
+                record.format.label(),
+                optional(record.score.map(|s| format!("{s:.4}"))),
+                optional(record.width),
+                optional(record.height),
+                optional(record.partition),
+                record.outputs.join(";"),
+            )?;
+        }
+        std::fs::write(path, buf)?;
+        Ok(())
+    }
+}
+
+fn optional<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -14,14 +15,21 @@ pub enum ArgosError {
     #[error("pattern build error")]
     PatternBuild(#[from] aho_corasick::BuildError),
 
-    #[error("validation failed: {kind}")]
-    Validation { kind: ValidationKind },
+    #[error("validation failed: {kind} at byte {offset}")]
+    Validation { kind: ValidationKind, offset: u64 },
 
     #[error("audit serialization error")]
     AuditSerialization(#[from] serde_json::Error),
+
+    #[error("source device changed during the session")]
+    SourceChanged,
+
+    #[error("output filesystem is full")]
+    OutputFull,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ValidationKind {
     MissingSoi,
     MissingEoi,
@@ -38,6 +46,18 @@ impl std::fmt::Display for ValidationKind {
     }
 }
 
+impl ArgosError {
+    pub fn with_offset(self, base: u64) -> Self {
+        match self {
+            ArgosError::Validation { kind, offset } => ArgosError::Validation {
+                kind,
+                offset: base + offset,
+            },
+            other => other,
+        }
+    }
+}
+
 impl From<rustix::io::Errno> for ArgosError {
     fn from(e: rustix::io::Errno) -> Self {
         ArgosError::Io(e.into())
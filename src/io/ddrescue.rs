@@ -0,0 +1,139 @@
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+const MAPFILE_EXTENSION: &str = "map";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockStatus {
+    NonTried,
+    NonTrimmed,
+    NonScraped,
+    BadSector,
+    Finished,
+}
+
+impl BlockStatus {
+    fn symbol(self) -> char {
+        match self {
+            BlockStatus::NonTried => '?',
+            BlockStatus::NonTrimmed => '*',
+            BlockStatus::NonScraped => '/',
+            BlockStatus::BadSector => '-',
+            BlockStatus::Finished => '+',
+        }
+    }
+
+    fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "?" => Some(BlockStatus::NonTried),
+            "*" => Some(BlockStatus::NonTrimmed),
+            "/" => Some(BlockStatus::NonScraped),
+            "-" => Some(BlockStatus::BadSector),
+            "+" => Some(BlockStatus::Finished),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MapBlock {
+    pub offset: u64,
+    pub length: u64,
+    pub status: BlockStatus,
+}
+
+pub fn parse(text: &str) -> Option<Vec<MapBlock>> {
+    let mut lines = text
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .filter(|line| !line.is_empty());
+    lines.next()?;
+    lines
+        .map(|line| {
+            let mut fields = line.split_whitespace();
+            let block = MapBlock {
+                offset: parse_number(fields.next()?)?,
+                length: parse_number(fields.next()?)?,
+                status: BlockStatus::from_symbol(fields.next()?)?,
+            };
+            (fields.next().is_none() && block.offset.checked_add(block.length).is_some())
+                .then_some(block)
+        })
+        .collect()
+}
+
+pub fn unreadable_ranges(blocks: &[MapBlock]) -> Vec<(u64, u64)> {
+    let mut ranges: Vec<(u64, u64)> = Vec::new();
+    for block in blocks
+        .iter()
+        .filter(|b| b.status != BlockStatus::Finished && b.length > 0)
+    {
+        match ranges.last_mut() {
+            Some((offset, length)) if *offset + *length == block.offset => *length += block.length,
+            _ => ranges.push((block.offset, block.length)),
+        }
+    }
+    ranges
+}
+
+pub fn render(unreadable: &[(u64, u64)], size: u64) -> String {
+    let mut blocks = Vec::with_capacity(unreadable.len() * 2 + 1);
+    let mut cursor = 0_u64;
+    for &(offset, length) in unreadable {
+        let start = offset.min(size);
+        let end = offset.saturating_add(length).min(size);
+        if start > cursor {
+            blocks.push((cursor, start - cursor, BlockStatus::Finished));
+        }
+        if end > start.max(cursor) {
+            blocks.push((
+                start.max(cursor),
+                end - start.max(cursor),
+                BlockStatus::BadSector,
+            ));
+        }
+        cursor = cursor.max(end);
+    }
+    if size > cursor {
+        blocks.push((cursor, size - cursor, BlockStatus::Finished));
+    }
+
+    let mut out = format!(
+        "# Mapfile. Created by {} version {}\n# current_pos  current_status  current_pass\n{:#010x}     +               1\n#      pos        size  status\n",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        0,
+    );
+    for (offset, length, status) in blocks {
+        let _ = writeln!(out, "{offset:#010x}  {length:#010x}  {}", status.symbol());
+    }
+    out
+}
+
+pub fn sibling_mapfile(source: &Path) -> Option<PathBuf> {
+    if !source.is_file() {
+        return None;
+    }
+    let mut appended = source.as_os_str().to_owned();
+    appended.push(".");
+    appended.push(MAPFILE_EXTENSION);
+    [
+        source.with_extension(MAPFILE_EXTENSION),
+        PathBuf::from(appended),
+    ]
+    .into_iter()
+    .find(|candidate| candidate != source && candidate.is_file())
+}
+
+fn parse_number(field: &str) -> Option<u64> {
+    if let Some(hex) = field
+        .strip_prefix("0x")
+        .or_else(|| field.strip_prefix("0X"))
+    {
+        return u64::from_str_radix(hex, 16).ok();
+    }
+    if field.len() > 1 && field.starts_with('0') {
+        return u64::from_str_radix(&field[1..], 8).ok();
+    }
+    field.parse().ok()
+}
@@ -0,0 +1,140 @@
+use crate::carve::ImageFormat;
+use crate::carve::matcher::Matcher;
+use crate::carve::ssd::patterns::PatternKind;
+use crate::error::ArgosError;
+use crate::io::partition;
+use crate::io::volume::{self, FileSystem};
+use crate::io::{BlockSource, read_head};
+use crate::validate::{StructureStep, jpeg, png};
+
+pub const SAMPLE_WINDOWS: u64 = 16;
+pub const WINDOW_BYTES: u64 = 1024 * 1024;
+const MIN_SAMPLED_WINDOWS: u64 = 4;
+const UNIFORM_CHI_SQUARE: f64 = 400.0;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EncryptionAssessment {
+    pub containers: Vec<(u64, FileSystem)>,
+    pub sampled_windows: u64,
+    pub uniform_windows: u64,
+    pub signature_hits: u64,
+}
+
+impl EncryptionAssessment {
+    pub fn looks_encrypted(&self) -> bool {
+        !self.containers.is_empty()
+            || (self.sampled_windows >= MIN_SAMPLED_WINDOWS
+                && self.uniform_windows * 10 >= self.sampled_windows * 9
+                && self.signature_hits == 0)
+    }
+
+    pub fn warning(&self) -> Option<String> {
+        if !self.looks_encrypted() {
+            return None;
+        }
+        let evidence = match self.containers.first() {
+            Some((offset, FileSystem::Bitlocker)) => {
+                format!("a BitLocker volume starts at byte {offset}")
+            }
+            Some((offset, _)) => format!("a LUKS container starts at byte {offset}"),
+            None => format!(
+                "{} of {} sampled 1 MiB windows are indistinguishable from random data and none holds a JPEG or PNG header",
+                self.uniform_windows, self.sampled_windows
+            ),
+        };
+        Some(format!(
+            "The source looks encrypted: {evidence}. Carving encrypted data recovers nothing; unlock the volume and scan the unlocked device instead."
+        ))
+    }
+}
+
+pub fn assess(source: &impl BlockSource, size: u64) -> Result<EncryptionAssessment, ArgosError> {
+    let matcher = Matcher::new()?;
+    let table =
+        read_head(source, size, 0, partition::PROBE_BYTES).and_then(|head| partition::parse(&head));
+    let containers = partition::volume_starts(table.as_ref())
+        .into_iter()
+        .filter_map(|(offset, _)| {
+            let head = read_head(source, size, offset, volume::PROBE_BYTES)?;
+            match volume::probe(&head)? {
+                filesystem @ (FileSystem::Bitlocker | FileSystem::Luks) => {
+                    Some((offset, filesystem))
+                }
+                _ => None,
+            }
+        })
+        .collect();
+
+    let mut assessment = EncryptionAssessment {
+        containers,
+        ..EncryptionAssessment::default()
+    };
+    for offset in window_offsets(size, source.sector_size() as u64) {
+        let Some(window) = read_head(source, size, offset, WINDOW_BYTES) else {
+            continue;
+        };
+        assessment.sampled_windows += 1;
+        if chi_square(&window) < UNIFORM_CHI_SQUARE {
+            assessment.uniform_windows += 1;
+        }
+        assessment.signature_hits += signature_hits(&matcher, &window);
+    }
+    Ok(assessment)
+}
+
+pub fn chi_square(window: &[u8]) -> f64 {
+    let mut counts = [0u64; 256];
+    for &byte in window {
+        counts[byte as usize] += 1;
+    }
+    let expected = window.len() as f64 / 256.0;
+    counts
+        .iter()
+        .map(|&count| {
+            let delta = count as f64 - expected;
+            delta * delta / expected
+        })
+        .sum()
+}
+
+fn window_offsets(size: u64, sector_size: u64) -> Vec<u64> {
+    let windows = SAMPLE_WINDOWS.min(size / WINDOW_BYTES);
+    if windows == 0 {
+        return Vec::new();
+    }
+    let step = (size - WINDOW_BYTES) / windows.saturating_sub(1).max(1);
+    (0..windows)
+        .map(|i| {
+            let offset = step * i;
+            offset - offset % sector_size.max(1)
+        })
+        .collect()
+}
+
+fn signature_hits(matcher: &Matcher, window: &[u8]) -> u64 {
+    matcher
+        .find(window, 0, 0)
+        .filter(|mat| match mat.kind {
+            PatternKind::Header(format) => {
+                let after = mat.offset as usize + mat.len;
+                opens_structure(format, window.get(after..).unwrap_or_default())
+            }
+            PatternKind::Footer(_) => false,
+        })
+        .count() as u64
+}
+
+fn opens_structure(format: ImageFormat, bytes: &[u8]) -> bool {
+    let step = |bytes: &[u8]| match format {
+        ImageFormat::Jpeg => jpeg::header_segment_step(bytes),
+        ImageFormat::Png => png::chunk_step(bytes),
+    };
+    let StructureStep::Skip(len) = step(bytes) else {
+        return false;
+    };
+    let rest = usize::try_from(len)
+        .ok()
+        .and_then(|len| bytes.get(len..))
+        .unwrap_or_default();
+    step(rest) != StructureStep::End
+}
@@ -0,0 +1,304 @@
+use serde::{Deserialize, Serialize};
+
+use crate::io::partition::{le_u32, le_u64};
+
+const SECTOR: usize = 512;
+const LABEL_SECTORS: usize = 4;
+const LABEL_ID: &[u8; 8] = b"LABELONE";
+const LABEL_TYPE_OFFSET: usize = 24;
+const LABEL_TYPE: &[u8; 8] = b"LVM2 001";
+const PV_HEADER_OFFSET: usize = 20;
+const UUID_LEN: usize = 32;
+const LOCATION_LEN: usize = 16;
+const MDA_MAGIC: &[u8; 16] = b" LVM2 x[5A%r0N*>";
+const MDA_HEADER_LEN: u64 = 512;
+const MAX_METADATA_BYTES: u64 = 1024 * 1024;
+const MAX_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetadataArea {
+    pub pv_uuid: String,
+    pub ranges: Vec<(u64, u64)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LogicalVolume {
+    pub name: String,
+    pub segments: Vec<LinearSegment>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LinearSegment {
+    pub offset: u64,
+    pub length: u64,
+}
+
+pub fn has_label(head: &[u8]) -> bool {
+    label_start(head).is_some()
+}
+
+pub fn metadata_area(head: &[u8]) -> Option<MetadataArea> {
+    let label = label_start(head)?;
+    let pv_offset = head.get(label + PV_HEADER_OFFSET..label + PV_HEADER_OFFSET + 4)?;
+    let pv = label.checked_add(le_u32(pv_offset) as usize)?;
+    let pv_uuid = std::str::from_utf8(head.get(pv..pv.checked_add(UUID_LEN)?)?)
+        .ok()?
+        .to_owned();
+    let data_areas = pv + UUID_LEN + 8;
+    let metadata_areas = data_areas + LOCATION_LEN * (locations(head, data_areas)?.len() + 1);
+    let (mda_offset, _) = *locations(head, metadata_areas)?.first()?;
+
+    let mda_start = usize::try_from(mda_offset).ok()?;
+    let mda = head.get(mda_start..mda_start.checked_add(MDA_HEADER_LEN as usize)?)?;
+    if &mda[4..20] != MDA_MAGIC || le_u64(&mda[24..]) != mda_offset {
+        return None;
+    }
+    let area_size = le_u64(&mda[32..]);
+    let text_offset = le_u64(&mda[40..]);
+    let text_size = le_u64(&mda[48..]);
+    if text_size == 0 || text_size > MAX_METADATA_BYTES || text_offset >= area_size {
+        return None;
+    }
+    let first = text_size.min(area_size - text_offset);
+    let mut ranges = vec![(mda_offset.checked_add(text_offset)?, first)];
+    if first < text_size {
+        ranges.push((mda_offset.checked_add(MDA_HEADER_LEN)?, text_size - first));
+    }
+    Some(MetadataArea { pv_uuid, ranges })
+}
+
+pub fn logical_volumes(area: &MetadataArea, text: &[u8], base: u64) -> Vec<LogicalVolume> {
+    let text = text.split(|&b| b == 0).next().unwrap_or_default();
+    let mut parser = Parser { text, pos: 0 };
+    parser
+        .section(0)
+        .and_then(|root| {
+            root.iter().find_map(|(_, value)| match value {
+                Value::Section(vg) => volume_group_layout(vg, &area.pv_uuid, base),
+                _ => None,
+            })
+        })
+        .unwrap_or_default()
+}
+
+fn label_start(head: &[u8]) -> Option<usize> {
+    (0..LABEL_SECTORS)
+        .map(|sector| sector * SECTOR)
+        .find(|&start| {
+            head.get(start..start + LABEL_ID.len()) == Some(LABEL_ID)
+                && head.get(start + LABEL_TYPE_OFFSET..start + LABEL_TYPE_OFFSET + LABEL_TYPE.len())
+                    == Some(LABEL_TYPE)
+        })
+}
+
+fn locations(head: &[u8], start: usize) -> Option<Vec<(u64, u64)>> {
+    let mut found = Vec::new();
+    let mut cursor = start;
+    loop {
+        let entry = head.get(cursor..cursor + LOCATION_LEN)?;
+        let location = (le_u64(entry), le_u64(&entry[8..]));
+        if location == (0, 0) {
+            return Some(found);
+        }
+        found.push(location);
+        cursor += LOCATION_LEN;
+    }
+}
+
+fn volume_group_layout(
+    vg: &[(String, Value)],
+    pv_uuid: &str,
+    base: u64,
+) -> Option<Vec<LogicalVolume>> {
+    let extent_bytes = number(vg, "extent_size")?.checked_mul(SECTOR as u64)?;
+    let (pv_name, pv) = sections(section(vg, "physical_volumes")?)
+        .find(|(_, pv)| text(pv, "id").is_some_and(|id| id.replace('-', "") == pv_uuid))?;
+    let data_start = base.checked_add(number(pv, "pe_start")?.checked_mul(SECTOR as u64)?)?;
+
+    let volumes = section(vg, "logical_volumes")
+        .map(|lvs| {
+            sections(lvs)
+                .filter_map(|(name, lv)| {
+                    let segments: Vec<LinearSegment> = sections(lv)
+                        .filter(|(key, _)| key.starts_with("segment"))
+                        .filter_map(|(_, segment)| {
+                            linear_segment(segment, pv_name, data_start, extent_bytes)
+                        })
+                        .collect();
+                    (!segments.is_empty()).then(|| LogicalVolume {
+                        name: name.to_owned(),
+                        segments,
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Some(volumes)
+}
+
+fn linear_segment(
+    segment: &[(String, Value)],
+    pv_name: &str,
+    data_start: u64,
+    extent_bytes: u64,
+) -> Option<LinearSegment> {
+    if text(segment, "type")? != "striped" || number(segment, "stripe_count")? != 1 {
+        return None;
+    }
+    let Some(Value::List(stripes)) = get(segment, "stripes") else {
+        return None;
+    };
+    let [Value::Text(stripe_pv), Value::Number(first_extent)] = stripes.as_slice() else {
+        return None;
+    };
+    if stripe_pv != pv_name {
+        return None;
+    }
+    Some(LinearSegment {
+        offset: data_start.checked_add(first_extent.checked_mul(extent_bytes)?)?,
+        length: number(segment, "extent_count")?.checked_mul(extent_bytes)?,
+    })
+}
+
+#[derive(Debug)]
+enum Value {
+    Number(u64),
+    Text(String),
+    List(Vec<Value>),
+    Section(Vec<(String, Value)>),
+}
+
+fn get<'a>(entries: &'a [(String, Value)], key: &str) -> Option<&'a Value> {
+    entries.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+}
+
+fn number(entries: &[(String, Value)], key: &str) -> Option<u64> {
+    match get(entries, key)? {
+        Value::Number(n) => Some(*n),
+        _ => None,
+    }
+}
+
+fn text<'a>(entries: &'a [(String, Value)], key: &str) -> Option<&'a str> {
+    match get(entries, key)? {
+        Value::Text(s) => Some(s),
+        _ => None,
+    }
+}
+
+fn section<'a>(entries: &'a [(String, Value)], key: &str) -> Option<&'a [(String, Value)]> {
+    match get(entries, key)? {
+        Value::Section(s) => Some(s),
+        _ => None,
+    }
+}
+
+fn sections(entries: &[(String, Value)]) -> impl Iterator<Item = (&str, &[(String, Value)])> {
+    entries.iter().filter_map(|(key, value)| match value {
+        Value::Section(s) => Some((key.as_str(), s.as_slice())),
+        _ => None,
+    })
+}
+
+struct Parser<'a> {
+    text: &'a [u8],
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&mut self) -> Option<u8> {
+        loop {
+            match self.text.get(self.pos)? {
+                b if b.is_ascii_whitespace() => self.pos += 1,
+                b'#' => {
+                    while self.text.get(self.pos).is_some_and(|&b| b != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                &b => return Some(b),
+            }
+        }
+    }
+
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        (self.peek()? == byte).then(|| self.pos += 1)
+    }
+
+    fn word(&mut self) -> Option<String> {
+        self.peek()?;
+        let start = self.pos;
+        while self
+            .text
+            .get(self.pos)
+            .is_some_and(|&b| b.is_ascii_alphanumeric() || b"_.+-".contains(&b))
+        {
+            self.pos += 1;
+        }
+        (self.pos > start).then(|| String::from_utf8_lossy(&self.text[start..self.pos]).into())
+    }
+
+    fn quoted(&mut self) -> Option<String> {
+        self.expect(b'"')?;
+        let mut bytes = Vec::new();
+        loop {
+            let b = *self.text.get(self.pos)?;
+            self.pos += 1;
+            match b {
+                b'"' => return String::from_utf8(bytes).ok(),
+                b'\\' => {
+                    bytes.push(*self.text.get(self.pos)?);
+                    self.pos += 1;
+                }
+                _ => bytes.push(b),
+            }
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Option<Value> {
+        match self.peek()? {
+            b'"' => self.quoted().map(Value::Text),
+            b'[' if depth < MAX_DEPTH => {
+                self.pos += 1;
+                let mut items = Vec::new();
+                while self.peek()? != b']' {
+                    items.push(self.value(depth + 1)?);
+                    if self.peek()? == b',' {
+                        self.pos += 1;
+                    }
+                }
+                self.pos += 1;
+                Some(Value::List(items))
+            }
+            _ => {
+                let word = self.word()?;
+                Some(word.parse().map_or(Value::Text(word), Value::Number))
+            }
+        }
+    }
+
+    fn section(&mut self, depth: usize) -> Option<Vec<(String, Value)>> {
+        let mut entries = Vec::new();
+        loop {
+            match self.peek() {
+                None | Some(b'}') => return Some(entries),
+                Some(_) => {
+                    let key = self.word()?;
+                    let value = match self.peek()? {
+                        b'=' => {
+                            self.pos += 1;
+                            self.value(depth)?
+                        }
+                        b'{' if depth < MAX_DEPTH => {
+                            self.pos += 1;
+                            let body = self.section(depth + 1)?;
+                            self.expect(b'}')?;
+                            Value::Section(body)
+                        }
+                        _ => return None,
+                    };
+                    entries.push((key, value));
+                }
+            }
+        }
+    }
+}
@@ -8,6 +8,12 @@ use rustix::io::{Errno, pread};
 
 use crate::error::ArgosError;
 
+pub mod ddrescue;
+pub mod encryption;
+pub mod lvm;
+pub mod partition;
+pub mod volume;
+
 pub struct AlignedBuf {
     ptr: *mut u8,
     len: usize,
@@ -75,9 +81,18 @@ impl fmt::Debug for AlignedBuf {
     }
 }
 
+const UNALIGNED_READ_BLOCK: usize = 64 * 1024;
+
 pub struct SourceDevice {
     fd: std::os::fd::OwnedFd,
     sector_size: usize,
+    last_block: parking_lot::Mutex<Option<CachedBlock>>,
+}
+
+#[derive(Debug)]
+struct CachedBlock {
+    offset: u64,
+    buf: AlignedBuf,
 }
 
 impl SourceDevice {
@@ -85,7 +100,11 @@ impl SourceDevice {
         let flags = OFlags::RDONLY | OFlags::DIRECT | OFlags::NOATIME;
         let fd = open(path, flags, Mode::from_raw_mode(0)).map_err(ArgosError::from)?;
         let sector_size = 4096;
-        Ok(Self { fd, sector_size })
+        Ok(Self {
+            fd,
+            sector_size,
+            last_block: parking_lot::Mutex::new(None),
+        })
     }
 
     pub fn sector_size(&self) -> usize {
@@ -100,14 +119,56 @@ impl SourceDevice {
         Ok(seek(&self.fd, SeekFrom::End(0))?)
     }
 
-    fn read_at(&self, buf: &mut AlignedBuf, offset: u64) -> Result<usize, ArgosError> {
-        let n = pread(&self.fd, buf.as_mut_slice(), offset).map_err(ArgosError::from)?;
-        Ok(n)
+    pub fn read_range(&self, buf: &mut [u8], offset: u64) -> Result<usize, ArgosError> {
+        if self.is_aligned(buf, offset) {
+            let n = pread(&self.fd, buf, offset).map_err(ArgosError::from)?;
+            return Ok(n);
+        }
+        self.read_unaligned(buf, offset)
     }
 
-    pub fn read_range(&self, buf: &mut [u8], offset: u64) -> Result<usize, ArgosError> {
-        let n = pread(&self.fd, buf, offset).map_err(ArgosError::from)?;
-        Ok(n)
+    fn is_aligned(&self, buf: &[u8], offset: u64) -> bool {
+        let align = self.sector_size;
+        offset % align as u64 == 0 && buf.len() % align == 0 && (buf.as_ptr() as usize) % align == 0
+    }
+
+    fn read_unaligned(&self, buf: &mut [u8], offset: u64) -> Result<usize, ArgosError> {
+        let mut cache = self.last_block.lock();
+        let mut copied = 0;
+        while copied < buf.len() {
+            let position = offset + copied as u64;
+            let block_offset = position - position % UNALIGNED_READ_BLOCK as u64;
+            let block = match cache.take() {
+                Some(cached) if cached.offset == block_offset => cached,
+                previous => self.load_block(previous, block_offset)?,
+            };
+            let within = (position - block_offset) as usize;
+            let available = block.buf.as_slice().get(within..).unwrap_or_default();
+            let n = available.len().min(buf.len() - copied);
+            buf[copied..copied + n].copy_from_slice(&available[..n]);
+            copied += n;
+            let exhausted = block.buf.as_slice().len() < UNALIGNED_READ_BLOCK;
+            *cache = Some(block);
+            if n == 0 || (exhausted && copied < buf.len()) {
+                break;
+            }
+        }
+        Ok(copied)
+    }
+
+    fn load_block(
+        &self,
+        previous: Option<CachedBlock>,
+        offset: u64,
+    ) -> Result<CachedBlock, ArgosError> {
+        let mut buf = match previous {
+            Some(cached) => cached.buf,
+            None => AlignedBuf::with_capacity(UNALIGNED_READ_BLOCK, self.sector_size)?,
+        };
+        buf.set_len(UNALIGNED_READ_BLOCK);
+        let n = pread(&self.fd, buf.as_mut_slice(), offset).map_err(ArgosError::from)?;
+        buf.set_len(n);
+        Ok(CachedBlock { offset, buf })
     }
 }
 
@@ -119,6 +180,54 @@ impl fmt::Debug for SourceDevice {
     }
 }
 
+pub trait BlockSource {
+    fn sector_size(&self) -> usize;
+    fn read_range(&self, buf: &mut [u8], offset: u64) -> Result<usize, ArgosError>;
+    fn next_data(&self, _offset: u64) -> Option<u64> {
+        None
+    }
+    fn read_exact_at(&self, buf: &mut [u8], offset: u64) -> Result<(), ArgosError> {
+        let mut filled = 0;
+        while filled < buf.len() {
+            match self.read_range(&mut buf[filled..], offset + filled as u64)? {
+                0 => return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into()),
+                n => filled += n,
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn read_head(
+    source: &impl BlockSource,
+    size: u64,
+    offset: u64,
+    length: u64,
+) -> Option<Vec<u8>> {
+    let len = usize::try_from(length.min(size.checked_sub(offset)?)).ok()?;
+    let mut head = vec![0u8; len];
+    source.read_exact_at(&mut head, offset).ok()?;
+    Some(head)
+}
+
+impl BlockSource for SourceDevice {
+    fn sector_size(&self) -> usize {
+        SourceDevice::sector_size(self)
+    }
+
+    fn read_range(&self, buf: &mut [u8], offset: u64) -> Result<usize, ArgosError> {
+        SourceDevice::read_range(self, buf, offset)
+    }
+
+    fn next_data(&self, offset: u64) -> Option<u64> {
+        match rustix::fs::seek(&self.fd, rustix::fs::SeekFrom::Data(offset)) {
+            Ok(position) => Some(position),
+            Err(rustix::io::Errno::NXIO) => self.size().ok(),
+            Err(_) => None,
+        }
+    }
+}
+
 pub struct OutputSink {
     base_dir: std::path::PathBuf,
 }
@@ -131,11 +240,32 @@ impl OutputSink {
         })
     }
 
-    pub fn create_file(&self, name: &str) -> Result<std::io::BufWriter<std::fs::File>, ArgosError> {
+    pub fn write_file(&self, name: &str, bytes: &[u8]) -> Result<(), ArgosError> {
         let path = self.base_dir.join(name);
-        let file = std::fs::File::create(&path)?;
-        let blksize = Self::blksize(&path)?;
-        Ok(std::io::BufWriter::with_capacity(blksize, file))
+        let written = Self::write_all(&path, bytes);
+        if written.is_err() {
+            std::fs::remove_file(&path).ok();
+        }
+        written
+    }
+
+    fn write_all(path: &Path, bytes: &[u8]) -> Result<(), ArgosError> {
+        let file = std::fs::File::create(path)?;
+        let mut writer = std::io::BufWriter::with_capacity(Self::blksize(path)?, file);
+        std::io::Write::write_all(&mut writer, bytes)?;
+        writer.into_inner().map_err(|e| e.into_error())?;
+        Ok(())
+    }
+
+    #[cfg(unix)]
+    pub fn available_bytes(&self) -> Result<u64, ArgosError> {
+        let stat = rustix::fs::statvfs(&self.base_dir)?;
+        Ok(stat.f_bavail.saturating_mul(stat.f_frsize))
+    }
+
+    #[cfg(not(unix))]
+    pub fn available_bytes(&self) -> Result<u64, ArgosError> {
+        Err(ArgosError::Unsupported)
     }
 
     #[cfg(unix)]
@@ -157,17 +287,20 @@ impl fmt::Debug for OutputSink {
     }
 }
 
-pub struct BlockReader<'a> {
-    device: &'a SourceDevice,
+pub struct BlockReader<'a, S: BlockSource = SourceDevice> {
+    device: &'a S,
     buf: AlignedBuf,
     offset: u64,
     end: u64,
     sector_size: usize,
     bad_sectors: Vec<(u64, u64)>,
+    salvage_end: u64,
+    ladder_exhausted: bool,
+    salvaged_sectors: u64,
 }
 
-impl<'a> BlockReader<'a> {
-    pub fn new(device: &'a SourceDevice, buf: AlignedBuf, end: u64) -> Self {
+impl<'a, S: BlockSource> BlockReader<'a, S> {
+    pub fn new(device: &'a S, buf: AlignedBuf, end: u64) -> Self {
         let sector_size = device.sector_size();
         Self {
             device,
@@ -176,6 +309,9 @@ impl<'a> BlockReader<'a> {
             end,
             sector_size,
             bad_sectors: Vec::new(),
+            salvage_end: 0,
+            ladder_exhausted: false,
+            salvaged_sectors: 0,
         }
     }
 
@@ -183,39 +319,106 @@ impl<'a> BlockReader<'a> {
         &self.bad_sectors
     }
 
-    pub fn try_next(&mut self) -> Result<Option<&[u8]>, ArgosError> {
+    pub fn salvaged_sectors(&self) -> u64 {
+        self.salvaged_sectors
+    }
+
+    pub fn try_next(&mut self) -> Result<Option<(u64, &[u8])>, ArgosError> {
         while self.offset < self.end {
+            if self.offset < self.salvage_end {
+                if let Some((start, len)) = self.salvage_run()? {
+                    return Ok(Some((start, &self.buf.as_slice()[..len])));
+                }
+                continue;
+            }
             let remaining = (self.end - self.offset) as usize;
             let to_read = self.buf.capacity().min(remaining);
             let to_read = align_down(to_read, self.sector_size);
             if to_read == 0 {
                 return Ok(None);
             }
+            let hole = self
+                .device
+                .next_data(self.offset)
+                .map_or(0, |data| data.saturating_sub(self.offset));
+            let hole = align_down(hole.min(to_read as u64) as usize, self.sector_size);
+            if hole > 0 {
+                let start = self.offset;
+                self.buf.set_len(hole);
+                self.buf.as_mut_slice().fill(0);
+                self.offset += hole as u64;
+                return Ok(Some((start, self.buf.as_slice())));
+            }
             self.buf.set_len(to_read);
-            match self.device.read_at(&mut self.buf, self.offset) {
+            match self.device.read_range(self.buf.as_mut_slice(), self.offset) {
+                Ok(0) => return Ok(None),
                 Ok(n) => {
+                    let start = self.offset;
                     self.buf.set_len(n);
                     self.offset += n as u64;
-                    return Ok(Some(self.buf.as_slice()));
+                    self.ladder_exhausted = false;
+                    return Ok(Some((start, self.buf.as_slice())));
                 }
                 Err(ArgosError::Io(ref e)) if is_bad_sector_error(e) => {
-                    self.bad_sectors.push((self.offset, to_read as u64));
-                    self.offset += to_read as u64;
+                    if self.ladder_exhausted {
+                        self.bad_sectors.push((self.offset, to_read as u64));
+                        self.offset += to_read as u64;
+                    } else {
+                        self.salvage_end = self.offset + to_read as u64;
+                    }
                 }
                 Err(e) => return Err(e),
             }
         }
         Ok(None)
     }
+
+    fn salvage_run(&mut self) -> Result<Option<(u64, usize)>, ArgosError> {
+        let mut run_start = self.offset;
+        let mut filled = 0_usize;
+        self.buf.set_len(self.buf.capacity());
+        while self.offset < self.salvage_end {
+            let sector = &mut self.buf.as_mut_slice()[filled..filled + self.sector_size];
+            match self.device.read_range(sector, self.offset) {
+                Ok(0) => {
+                    self.salvage_end = self.offset;
+                    self.end = self.offset;
+                }
+                Ok(n) => {
+                    filled += n;
+                    self.offset += n as u64;
+                    self.salvaged_sectors += 1;
+                    if n < self.sector_size {
+                        self.salvage_end = self.offset;
+                    }
+                }
+                Err(ArgosError::Io(ref e)) if is_bad_sector_error(e) => {
+                    self.bad_sectors
+                        .push((self.offset, self.sector_size as u64));
+                    self.offset += self.sector_size as u64;
+                    if filled > 0 {
+                        break;
+                    }
+                    run_start = self.offset;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        if filled == 0 {
+            self.ladder_exhausted = true;
+        }
+        Ok((filled > 0).then_some((run_start, filled)))
+    }
 }
 
-impl fmt::Debug for BlockReader<'_> {
+impl<S: BlockSource> fmt::Debug for BlockReader<'_, S> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("BlockReader")
             .field("offset", &self.offset)
             .field("end", &self.end)
             .field("sector_size", &self.sector_size)
             .field("bad_sector_count", &self.bad_sectors.len())
+            .field("salvaged_sectors", &self.salvaged_sectors)
             .finish_non_exhaustive()
     }
 }
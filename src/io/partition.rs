@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+
+use crate::io::volume::{self, FileSystem};
+
+pub const PROBE_BYTES: u64 = 1024 * 1024;
+
+const MBR_SIGNATURE_OFFSET: usize = 510;
+const MBR_TABLE_OFFSET: usize = 446;
+const MBR_ENTRY_LEN: usize = 16;
+const MBR_SECTOR: u64 = 512;
+const MBR_PROTECTIVE: u8 = 0xEE;
+const GPT_SIGNATURE: &[u8; 8] = b"EFI PART";
+const GPT_HEADER_MIN_LEN: usize = 92;
+const GPT_ENTRY_MIN_LEN: usize = 128;
+const GPT_NAME_UNITS: usize = 36;
+const GPT_LOGICAL_SECTORS: [u64; 2] = [512, 4096];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PartitionScheme {
+    Gpt,
+    Mbr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Partition {
+    pub index: u32,
+    pub offset: u64,
+    pub length: u64,
+    pub type_id: String,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartitionTable {
+    pub scheme: PartitionScheme,
+    pub logical_sector_size: u64,
+    pub partitions: Vec<Partition>,
+}
+
+impl PartitionTable {
+    pub fn containing(&self, offset: u64) -> Option<&Partition> {
+        self.partitions
+            .iter()
+            .find(|p| offset >= p.offset && offset - p.offset < p.length)
+    }
+}
+
+pub fn volume_starts(table: Option<&PartitionTable>) -> Vec<(u64, Option<u32>)> {
+    match table {
+        Some(table) => table
+            .partitions
+            .iter()
+            .map(|p| (p.offset, Some(p.index)))
+            .collect(),
+        None => vec![(0, None)],
+    }
+}
+
+pub fn parse(head: &[u8]) -> Option<PartitionTable> {
+    let mbr = head.get(..MBR_SIGNATURE_OFFSET + 2)?;
+    if mbr[MBR_SIGNATURE_OFFSET..] != [0x55, 0xAA] || is_volume_boot_record(head) {
+        return None;
+    }
+    let entries = mbr_entries(mbr);
+    if entries.iter().any(|(kind, _, _)| *kind == MBR_PROTECTIVE) {
+        if let Some(table) = GPT_LOGICAL_SECTORS
+            .iter()
+            .find_map(|&sector| parse_gpt(head, sector))
+        {
+            return Some(table);
+        }
+    }
+    Some(PartitionTable {
+        scheme: PartitionScheme::Mbr,
+        logical_sector_size: MBR_SECTOR,
+        partitions: entries
+            .into_iter()
+            .enumerate()
+            .map(|(slot, (kind, start, sectors))| Partition {
+                index: slot as u32 + 1,
+                offset: start * MBR_SECTOR,
+                length: sectors * MBR_SECTOR,
+                type_id: format!("0x{kind:02X}"),
+                name: None,
+            })
+            .collect(),
+    })
+}
+
+fn is_volume_boot_record(head: &[u8]) -> bool {
+    matches!(
+        volume::probe(head),
+        Some(FileSystem::Fat | FileSystem::Ntfs | FileSystem::Exfat | FileSystem::Bitlocker)
+    )
+}
+
+fn mbr_entries(mbr: &[u8]) -> Vec<(u8, u64, u64)> {
+    mbr[MBR_TABLE_OFFSET..MBR_SIGNATURE_OFFSET]
+        .chunks_exact(MBR_ENTRY_LEN)
+        .map(|entry| {
+            (
+                entry[4],
+                u64::from(le_u32(&entry[8..])),
+                u64::from(le_u32(&entry[12..])),
+            )
+        })
+        .filter(|&(kind, _, sectors)| kind != 0 && sectors != 0)
+        .collect()
+}
+
+fn parse_gpt(head: &[u8], sector: u64) -> Option<PartitionTable> {
+    let header_start = usize::try_from(sector).ok()?;
+    let header = head.get(header_start..header_start.checked_add(GPT_HEADER_MIN_LEN)?)?;
+    if &header[..8] != GPT_SIGNATURE {
+        return None;
+    }
+    let header_len = le_u32(&header[12..]) as usize;
+    if header_len < GPT_HEADER_MIN_LEN || header_len > usize::try_from(sector).ok()? {
+        return None;
+    }
+    let mut full_header = head
+        .get(header_start..header_start.checked_add(header_len)?)?
+        .to_vec();
+    let stored_crc = le_u32(&full_header[16..]);
+    full_header[16..20].fill(0);
+    if crc32fast::hash(&full_header) != stored_crc || le_u64(&full_header[24..]) != 1 {
+        return None;
+    }
+
+    let entries_lba = le_u64(&full_header[72..]);
+    let entry_count = le_u32(&full_header[80..]) as usize;
+    let entry_len = le_u32(&full_header[84..]) as usize;
+    if entry_len < GPT_ENTRY_MIN_LEN || entry_len % 8 != 0 {
+        return None;
+    }
+    let entries_start = usize::try_from(entries_lba.checked_mul(sector)?).ok()?;
+    let entries_len = entry_count.checked_mul(entry_len)?;
+    let entries = head.get(entries_start..entries_start.checked_add(entries_len)?)?;
+    if crc32fast::hash(entries) != le_u32(&full_header[88..]) {
+        return None;
+    }
+
+    let partitions = entries
+        .chunks_exact(entry_len)
+        .enumerate()
+        .filter(|(_, entry)| entry[..16].iter().any(|&b| b != 0))
+        .filter_map(|(slot, entry)| {
+            let first = le_u64(&entry[32..]);
+            let last = le_u64(&entry[40..]);
+            let sectors = last.checked_sub(first)?.checked_add(1)?;
+            Some(Partition {
+                index: slot as u32 + 1,
+                offset: first.checked_mul(sector)?,
+                length: sectors.checked_mul(sector)?,
+                type_id: format_guid(&entry[..16]),
+                name: gpt_name(&entry[56..56 + GPT_NAME_UNITS * 2]),
+            })
+        })
+        .collect();
+
+    Some(PartitionTable {
+        scheme: PartitionScheme::Gpt,
+        logical_sector_size: sector,
+        partitions,
+    })
+}
+
+fn format_guid(bytes: &[u8]) -> String {
+    format!(
+        "{:08X}-{:04X}-{:04X}-{}-{}",
+        le_u32(bytes),
+        u16::from_le_bytes([bytes[4], bytes[5]]),
+        u16::from_le_bytes([bytes[6], bytes[7]]),
+        hex::encode_upper(&bytes[8..10]),
+        hex::encode_upper(&bytes[10..16]),
+    )
+}
+
+fn gpt_name(raw: &[u8]) -> Option<String> {
+    let units: Vec<u16> = raw
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .take_while(|&unit| unit != 0)
+        .collect();
+    let name = String::from_utf16_lossy(&units);
+    (!name.is_empty()).then_some(name)
+}
+
+pub fn le_u32(bytes: &[u8]) -> u32 {
+    u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+}
+
+pub fn le_u64(bytes: &[u8]) -> u64 {
+    u64::from_le_bytes([
+        bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+    ])
+}
@@ -0,0 +1,148 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::io::lvm::{self, LogicalVolume};
+
+const BTRFS_MAGIC_OFFSET: usize = 0x10040;
+const BTRFS_MAGIC: &[u8; 8] = b"_BHRfS_M";
+const EXT_MAGIC_OFFSET: usize = 0x438;
+const EXT_MAGIC: [u8; 2] = [0x53, 0xEF];
+const OEM_ID_OFFSET: usize = 3;
+const NTFS_OEM_ID: &[u8; 8] = b"NTFS    ";
+const EXFAT_OEM_ID: &[u8; 8] = b"EXFAT   ";
+const BITLOCKER_OEM_ID: &[u8; 8] = b"-FVE-FS-";
+const FAT16_TYPE_OFFSET: usize = 0x36;
+const FAT32_TYPE_OFFSET: usize = 0x52;
+const BOOT_SIGNATURE_OFFSET: usize = 510;
+const HFS_PLUS_HEADER_OFFSET: usize = 1024;
+const HFS_PLUS_SIGNATURES: [[u8; 4]; 2] = [*b"H+\x00\x04", *b"HX\x00\x05"];
+const APFS_MAGIC_OFFSET: usize = 32;
+const APFS_MAGIC: &[u8; 4] = b"NXSB";
+const LUKS_MAGIC: &[u8; 6] = b"LUKS\xBA\xBE";
+const LUKS1_CIPHER_NAME: std::ops::Range<usize> = 8..40;
+const LUKS1_CIPHER_MODE: std::ops::Range<usize> = 40..72;
+const LUKS2_HEADER_SIZE_OFFSET: usize = 8;
+const LUKS2_JSON_OFFSET: usize = 4096;
+
+pub const PROBE_BYTES: u64 = (BTRFS_MAGIC_OFFSET + BTRFS_MAGIC.len()) as u64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileSystem {
+    Ext,
+    Ntfs,
+    Exfat,
+    Fat,
+    Btrfs,
+    Hfsplus,
+    Apfs,
+    Lvm2,
+    Luks,
+    Bitlocker,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Volume {
+    pub offset: u64,
+    pub partition: Option<u32>,
+    pub filesystem: FileSystem,
+    #[serde(default)]
+    pub luks: Option<LuksHeader>,
+    #[serde(default)]
+    pub logical_volumes: Vec<LogicalVolume>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LuksHeader {
+    pub version: u16,
+    pub cipher: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Luks2Metadata {
+    segments: BTreeMap<String, Luks2Segment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Luks2Segment {
+    encryption: Option<String>,
+}
+
+pub fn probe(head: &[u8]) -> Option<FileSystem> {
+    if bytes_at(head, OEM_ID_OFFSET, NTFS_OEM_ID) {
+        return Some(FileSystem::Ntfs);
+    }
+    if bytes_at(head, OEM_ID_OFFSET, EXFAT_OEM_ID) {
+        return Some(FileSystem::Exfat);
+    }
+    if bytes_at(head, OEM_ID_OFFSET, BITLOCKER_OEM_ID) {
+        return Some(FileSystem::Bitlocker);
+    }
+    if bytes_at(head, BOOT_SIGNATURE_OFFSET, &[0x55, 0xAA])
+        && (bytes_at(head, FAT16_TYPE_OFFSET, b"FAT1")
+            || bytes_at(head, FAT32_TYPE_OFFSET, b"FAT32"))
+    {
+        return Some(FileSystem::Fat);
+    }
+    if bytes_at(head, EXT_MAGIC_OFFSET, &EXT_MAGIC) {
+        return Some(FileSystem::Ext);
+    }
+    if bytes_at(head, BTRFS_MAGIC_OFFSET, BTRFS_MAGIC) {
+        return Some(FileSystem::Btrfs);
+    }
+    if HFS_PLUS_SIGNATURES
+        .iter()
+        .any(|signature| bytes_at(head, HFS_PLUS_HEADER_OFFSET, signature))
+    {
+        return Some(FileSystem::Hfsplus);
+    }
+    if bytes_at(head, APFS_MAGIC_OFFSET, APFS_MAGIC) {
+        return Some(FileSystem::Apfs);
+    }
+    if bytes_at(head, 0, LUKS_MAGIC) {
+        return Some(FileSystem::Luks);
+    }
+    if lvm::has_label(head) {
+        return Some(FileSystem::Lvm2);
+    }
+    None
+}
+
+pub fn luks_header(head: &[u8]) -> Option<LuksHeader> {
+    if !bytes_at(head, 0, LUKS_MAGIC) {
+        return None;
+    }
+    let version = u16::from_be_bytes([*head.get(6)?, *head.get(7)?]);
+    let cipher = match version {
+        1 => {
+            let name = nul_terminated(head.get(LUKS1_CIPHER_NAME)?);
+            let mode = nul_terminated(head.get(LUKS1_CIPHER_MODE)?);
+            (!name.is_empty()).then(|| format!("{name}-{mode}"))
+        }
+        2 => luks2_cipher(head),
+        _ => None,
+    };
+    Some(LuksHeader { version, cipher })
+}
+
+fn luks2_cipher(head: &[u8]) -> Option<String> {
+    let size_bytes = head.get(LUKS2_HEADER_SIZE_OFFSET..LUKS2_HEADER_SIZE_OFFSET + 8)?;
+    let header_size = u64::from_be_bytes(size_bytes.try_into().ok()?);
+    let end = usize::try_from(header_size).ok()?.min(head.len());
+    let json = head.get(LUKS2_JSON_OFFSET..end)?;
+    let json = json.split(|&b| b == 0).next()?;
+    let metadata: Luks2Metadata = serde_json::from_slice(json).ok()?;
+    metadata
+        .segments
+        .into_values()
+        .find_map(|segment| segment.encryption)
+}
+
+fn nul_terminated(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+fn bytes_at(head: &[u8], offset: usize, expected: &[u8]) -> bool {
+    head.get(offset..offset + expected.len()) == Some(expected)
+}
@@ -1,4 +1,5 @@
 use crate::error::{ArgosError, ValidationKind};
+use crate::validate::{Inspection, StructureStep};
 
 const SOI: u8 = 0xD8;
 const EOI: u8 = 0xD9;
@@ -9,17 +10,30 @@ const SOF0: u8 = 0xC0;
 const SOF1: u8 = 0xC1;
 const SOF2: u8 = 0xC2;
 const SOF3: u8 = 0xC3;
+const DRI: u8 = 0xDD;
+const COM: u8 = 0xFE;
+const APP_LOW: u8 = 0xE0;
+const APP_HIGH: u8 = 0xEF;
+const APP2: u8 = 0xE2;
+const ICC_TAG: &[u8] = b"ICC_PROFILE\0";
 const RST_LOW: u8 = 0xD0;
 const RST_HIGH: u8 = 0xD7;
 const MAX_DC_CATEGORY: u8 = 11;
 const MAX_AC_CATEGORY: u8 = 10;
 const COEFFICIENTS_PER_BLOCK: usize = 64;
 const ZERO_DOMINANCE_THRESHOLD: f32 = 0.8;
+pub const NON_BASELINE_SCORE: f32 = 0.5;
+const STANDARD_LUMINANCE_TABLE: [u16; 64] = [
+    16, 11, 10, 16, 24, 40, 51, 61, 12, 12, 14, 19, 26, 58, 60, 55, 14, 13, 16, 24, 40, 57, 69, 56,
+    14, 17, 22, 29, 51, 87, 80, 62, 18, 22, 37, 56, 68, 109, 103, 77, 24, 35, 55, 64, 81, 104, 113,
+    92, 49, 64, 78, 87, 103, 121, 120, 101, 72, 92, 95, 98, 112, 100, 103, 99,
+];
 
 #[derive(Debug, Clone)]
-struct Segment {
+struct Segment<'a> {
     marker: u8,
-    data: Vec<u8>,
+    start: usize,
+    data: &'a [u8],
 }
 
 #[derive(Debug, Clone)]
@@ -35,6 +49,7 @@ impl HuffmanLut {
         if segment_body.len() < 17 {
             return Err(ArgosError::Validation {
                 kind: ValidationKind::BadHuffmanTable,
+                offset: 0,
             });
         }
         let class = (segment_body[0] >> 4) & 0x0F;
@@ -42,6 +57,7 @@ impl HuffmanLut {
         if id > 3 {
             return Err(ArgosError::Validation {
                 kind: ValidationKind::BadHuffmanTable,
+                offset: 0,
             });
         }
         let mut bits = [0u8; 16];
@@ -50,6 +66,7 @@ impl HuffmanLut {
         if segment_body.len() < 17 + total {
             return Err(ArgosError::Validation {
                 kind: ValidationKind::BadHuffmanTable,
+                offset: 0,
             });
         }
         let values = segment_body[17..17 + total].to_vec();
@@ -73,6 +90,7 @@ impl HuffmanLut {
         if value_index != total {
             return Err(ArgosError::Validation {
                 kind: ValidationKind::BadHuffmanTable,
+                offset: 0,
             });
         }
         Ok((
@@ -149,6 +167,23 @@ impl<'a> BitReader<'a> {
         self.bit_buf &= (1u64 << self.bit_count).wrapping_sub(1);
         Some(value)
     }
+
+    fn resync_at_restart(&mut self, expected: u8) -> Option<usize> {
+        self.bit_buf = 0;
+        self.bit_count = 0;
+        let marker = match self.marker_seen.take() {
+            Some(marker) => marker,
+            None => {
+                if self.data.get(self.pos) != Some(&0xFF) {
+                    return None;
+                }
+                let marker = *self.data.get(self.pos + 1)?;
+                self.pos += 2;
+                marker
+            }
+        };
+        (marker == expected).then_some(self.pos - 2)
+    }
 }
 
 fn decode_symbol(bits: &mut BitReader, lut: &HuffmanLut) -> Option<u8> {
@@ -328,7 +363,8 @@ fn collect_huffman_luts(
     for seg in segments.iter().filter(|s| s.marker == DHT) {
         let mut offset = 0;
         while offset < seg.data.len() {
-            let (class, id, lut) = HuffmanLut::from_segment_data(&seg.data[offset..])?;
+            let (class, id, lut) = HuffmanLut::from_segment_data(&seg.data[offset..])
+                .map_err(|e| e.with_offset((seg.start + 4 + offset) as u64))?;
             let consumed = 17 + lut.values.len();
             if class == 0 {
                 dc_luts[id as usize] = Some(lut);
@@ -341,6 +377,33 @@ fn collect_huffman_luts(
     Ok((dc_luts, ac_luts))
 }
 
+pub fn header_segment_step(bytes: &[u8]) -> StructureStep {
+    let [first, marker, ..] = *bytes else {
+        return StructureStep::NeedMore;
+    };
+    if first != 0xFF {
+        return StructureStep::End;
+    }
+    if marker == 0xFF {
+        return StructureStep::Skip(1);
+    }
+    if marker == SOS
+        || marker == SOI
+        || marker == EOI
+        || marker < SOF0
+        || (RST_LOW..=RST_HIGH).contains(&marker)
+    {
+        return StructureStep::End;
+    }
+    let [_, _, high, low, ..] = *bytes else {
+        return StructureStep::NeedMore;
+    };
+    match u16::from_be_bytes([high, low]) {
+        len if len < 2 => StructureStep::End,
+        len => StructureStep::Skip(2 + u64::from(len)),
+    }
+}
+
 fn is_baseline_marker(marker: u8) -> bool {
     marker == SOF0
 }
@@ -350,16 +413,17 @@ fn is_sof_marker(marker: u8) -> bool {
 }
 
 #[derive(Debug)]
-struct ParsedJpeg {
-    segments: Vec<Segment>,
+struct ParsedJpeg<'a> {
+    segments: Vec<Segment<'a>>,
     entropy_start: usize,
-    entropy_end: usize,
+    entropy_end: Option<usize>,
 }
 
-fn parse_jpeg(data: &[u8]) -> Result<ParsedJpeg, ArgosError> {
+fn parse_jpeg(data: &[u8]) -> Result<ParsedJpeg<'_>, ArgosError> {
     if data.len() < 4 || data[0] != 0xFF || data[1] != SOI {
         return Err(ArgosError::Validation {
             kind: ValidationKind::MissingSoi,
+            offset: 0,
         });
     }
 
@@ -383,17 +447,20 @@ fn parse_jpeg(data: &[u8]) -> Result<ParsedJpeg, ArgosError> {
         if i + 3 >= data.len() {
             return Err(ArgosError::Validation {
                 kind: ValidationKind::TruncatedSegment,
+                offset: i as u64,
             });
         }
         let len = u16::from_be_bytes([data[i + 2], data[i + 3]]) as usize;
         if len < 2 || i + 2 + len > data.len() {
             return Err(ArgosError::Validation {
                 kind: ValidationKind::TruncatedSegment,
+                offset: i as u64,
             });
         }
         segments.push(Segment {
             marker,
-            data: data[i + 4..i + 2 + len].to_vec(),
+            start: i,
+            data: &data[i + 4..i + 2 + len],
         });
         i += 2 + len;
         if marker == SOS {
@@ -402,15 +469,11 @@ fn parse_jpeg(data: &[u8]) -> Result<ParsedJpeg, ArgosError> {
         }
     }
 
-    let entropy_end =
-        find_eoi_offset(data, entropy_start.unwrap_or(i)).ok_or(ArgosError::Validation {
-            kind: ValidationKind::MissingEoi,
-        })?;
-
+    let entropy_start = entropy_start.unwrap_or(i);
     Ok(ParsedJpeg {
         segments,
-        entropy_start: entropy_start.unwrap_or(entropy_end),
-        entropy_end,
+        entropy_start,
+        entropy_end: find_eoi_offset(data, entropy_start),
     })
 }
 
@@ -432,14 +495,113 @@ fn find_eoi_offset(data: &[u8], start: usize) -> Option<usize> {
     None
 }
 
-fn mcus_expected(frame: &Frame, scan: &[ScanComponent]) -> usize {
-    let max_h = scan.iter().map(|c| c.h_samp).max().unwrap_or(1).max(1) as usize;
-    let max_v = scan.iter().map(|c| c.v_samp).max().unwrap_or(1).max(1) as usize;
-    let pixels_per_mcu_w = max_h * 8;
-    let pixels_per_mcu_v = max_v * 8;
-    let mcus_w = (frame.width as usize).div_ceil(pixels_per_mcu_w);
-    let mcus_h = (frame.height as usize).div_ceil(pixels_per_mcu_v);
-    mcus_w.saturating_mul(mcus_h)
+#[derive(Debug, Clone, Copy, Default)]
+struct ScanWalk {
+    complete_scans: usize,
+    last_boundary: Option<usize>,
+    eoi: Option<usize>,
+}
+
+fn walk_scans(data: &[u8], entropy_start: usize) -> ScanWalk {
+    let mut walk = ScanWalk::default();
+    let mut started = 1;
+    let mut i = entropy_start;
+    while let Some(boundary) = next_marker(data, i) {
+        let marker = data[boundary + 1];
+        if marker == EOI {
+            walk.eoi = Some(boundary);
+            walk.complete_scans = started;
+            walk.last_boundary = Some(boundary);
+            return walk;
+        }
+        if marker != SOS && !is_inter_scan_marker(marker) {
+            return walk;
+        }
+        walk.complete_scans = started;
+        walk.last_boundary = Some(boundary);
+        let mut j = boundary;
+        loop {
+            let Some(&[0xFF, marker, hi, lo]) = data.get(j..j + 4) else {
+                return walk;
+            };
+            let len = u16::from_be_bytes([hi, lo]) as usize;
+            if len < 2 || j + 2 + len > data.len() {
+                return walk;
+            }
+            if marker == SOS {
+                if !plausible_scan_header(&data[j + 4..j + 2 + len]) {
+                    return walk;
+                }
+                started += 1;
+                i = j + 2 + len;
+                break;
+            }
+            if !is_inter_scan_marker(marker) {
+                return walk;
+            }
+            j += 2 + len;
+        }
+    }
+    walk
+}
+
+fn next_marker(data: &[u8], start: usize) -> Option<usize> {
+    let mut i = start;
+    while i + 1 < data.len() {
+        if data[i] == 0xFF {
+            let next = data[i + 1];
+            if next != 0x00 && next != 0xFF && !(RST_LOW..=RST_HIGH).contains(&next) {
+                return Some(i);
+            }
+        }
+        i += 1;
+    }
+    None
+}
+
+fn is_inter_scan_marker(marker: u8) -> bool {
+    matches!(marker, DHT | DQT | DRI | COM | APP_LOW..=APP_HIGH)
+}
+
+fn plausible_scan_header(body: &[u8]) -> bool {
+    let Some(&components) = body.first() else {
+        return false;
+    };
+    let components = components as usize;
+    if !(1..=4).contains(&components) || body.len() != 4 + 2 * components {
+        return false;
+    }
+    let spectral_start = body[1 + 2 * components];
+    let spectral_end = body[2 + 2 * components];
+    spectral_start <= spectral_end && spectral_end <= 63
+}
+
+fn is_progressive(parsed: &ParsedJpeg) -> bool {
+    parsed.segments.iter().any(|s| s.marker == SOF2)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct McuGrid {
+    per_row: usize,
+    rows: usize,
+    height_px: usize,
+}
+
+impl McuGrid {
+    fn new(frame: &Frame, scan: &[ScanComponent]) -> Self {
+        let max_h = scan.iter().map(|c| c.h_samp).max().unwrap_or(1).max(1) as usize;
+        let max_v = scan.iter().map(|c| c.v_samp).max().unwrap_or(1).max(1) as usize;
+        let height_px = max_v * 8;
+        Self {
+            per_row: (frame.width as usize).div_ceil(max_h * 8),
+            rows: (frame.height as usize).div_ceil(height_px),
+            height_px,
+        }
+    }
+
+    fn total(&self) -> usize {
+        self.per_row.saturating_mul(self.rows)
+    }
 }
 
 fn decode_mcu(
@@ -459,69 +621,345 @@ fn decode_mcu(
     Some(())
 }
 
-pub fn validate(data: &[u8]) -> Result<f32, ArgosError> {
-    let parsed = match parse_jpeg(data) {
-        Ok(p) => p,
-        Err(ArgosError::Validation { .. }) => return Ok(0.0),
-        Err(e) => return Err(e),
-    };
+fn restart_interval(segments: &[Segment]) -> usize {
+    segments
+        .iter()
+        .rev()
+        .find(|s| s.marker == DRI && s.data.len() >= 2)
+        .map_or(0, |s| u16::from_be_bytes([s.data[0], s.data[1]]) as usize)
+}
+
+#[derive(Debug)]
+struct BaselineScan {
+    sof_start: usize,
+    frame: Frame,
+    scan: Vec<ScanComponent>,
+    grid: McuGrid,
+    restart_interval: usize,
+    dc_luts: HuffmanLutTable,
+    ac_luts: HuffmanLutTable,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RestartBoundary {
+    entropy_offset: usize,
+    mcus_before: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ScanProgress {
+    decoded: usize,
+    last_restart: Option<RestartBoundary>,
+}
+
+impl BaselineScan {
+    fn decode(&self, entropy: &[u8]) -> ScanProgress {
+        let expected = self.grid.total();
+        let mut bits = BitReader::new(entropy);
+        let mut decoded = 0usize;
+        let mut last_restart = None;
+        let mut next_restart = RST_LOW;
+
+        while decoded < expected {
+            if self.restart_interval > 0 && decoded > 0 && decoded % self.restart_interval == 0 {
+                let Some(entropy_offset) = bits.resync_at_restart(next_restart) else {
+                    break;
+                };
+                last_restart = Some(RestartBoundary {
+                    entropy_offset,
+                    mcus_before: decoded,
+                });
+                next_restart = if next_restart == RST_HIGH {
+                    RST_LOW
+                } else {
+                    next_restart + 1
+                };
+            }
+            if decode_mcu(&mut bits, &self.scan, &self.dc_luts, &self.ac_luts).is_none() {
+                break;
+            }
+            decoded += 1;
+        }
+
+        ScanProgress {
+            decoded,
+            last_restart,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Assessment {
+    Rejected,
+    NonBaseline,
+    Baseline(Box<BaselineScan>),
+}
 
+fn assess(parsed: &ParsedJpeg) -> Assessment {
     let Some(sof) = parsed.segments.iter().find(|s| is_sof_marker(s.marker)) else {
-        return Ok(0.0);
+        return Assessment::Rejected;
     };
     let Some(sos_seg) = parsed.segments.iter().find(|s| s.marker == SOS) else {
-        return Ok(0.0);
+        return Assessment::Rejected;
     };
     let has_dht = parsed.segments.iter().any(|s| s.marker == DHT);
     let has_dqt = parsed.segments.iter().any(|s| s.marker == DQT);
     if !has_dht || !has_dqt {
-        return Ok(0.0);
+        return Assessment::Rejected;
     }
 
     if !is_baseline_marker(sof.marker) {
-        return Ok(0.5);
+        return Assessment::NonBaseline;
     }
 
-    let Some(frame) = parse_frame(&sof.data) else {
-        return Ok(0.0);
+    let Some(frame) = parse_frame(sof.data) else {
+        return Assessment::Rejected;
     };
 
     let mut qt_present = [false; 4];
     for seg in parsed.segments.iter().filter(|s| s.marker == DQT) {
-        record_quant_tables(&seg.data, &mut qt_present);
+        record_quant_tables(seg.data, &mut qt_present);
     }
     for comp in &frame.components {
         if comp.qt_idx >= 4 || !qt_present[comp.qt_idx as usize] {
-            return Ok(0.0);
+            return Assessment::Rejected;
         }
     }
 
-    let (dc_luts, ac_luts) = match collect_huffman_luts(&parsed.segments) {
+    let Ok((dc_luts, ac_luts)) = collect_huffman_luts(&parsed.segments) else {
+        return Assessment::Rejected;
+    };
+
+    let Some(scan) = parse_scan_components(sos_seg.data, &frame) else {
+        return Assessment::Rejected;
+    };
+
+    let grid = McuGrid::new(&frame, &scan);
+    if grid.total() == 0 {
+        return Assessment::Rejected;
+    }
+
+    Assessment::Baseline(Box::new(BaselineScan {
+        sof_start: sof.start,
+        frame,
+        scan,
+        grid,
+        restart_interval: restart_interval(&parsed.segments),
+        dc_luts,
+        ac_luts,
+    }))
+}
+
+pub fn validate(data: &[u8]) -> Result<f32, ArgosError> {
+    inspect(data).map(|inspection| inspection.score)
+}
+
+pub fn inspect(data: &[u8]) -> Result<Inspection, ArgosError> {
+    let parsed = match parse_jpeg(data) {
         Ok(p) => p,
-        Err(_) => return Ok(0.0),
+        Err(ArgosError::Validation { .. }) => return Ok(Inspection::default()),
+        Err(e) => return Err(e),
     };
+    Ok(Inspection {
+        score: score(data, &parsed),
+        dimensions: dimensions(&parsed),
+        icc_profile: icc_profile(&parsed),
+        quality: quality_estimate(&parsed),
+        ..Inspection::default()
+    })
+}
 
-    let Some(scan) = parse_scan_components(&sos_seg.data, &frame) else {
-        return Ok(0.0);
+fn score(data: &[u8], parsed: &ParsedJpeg) -> f32 {
+    let Some(entropy_end) = parsed.entropy_end else {
+        return 0.0;
     };
 
-    let expected_mcus = mcus_expected(&frame, &scan);
-    if expected_mcus == 0 {
-        return Ok(0.0);
+    match assess(parsed) {
+        Assessment::Rejected => 0.0,
+        Assessment::NonBaseline if is_progressive(parsed) => {
+            let walk = walk_scans(data, parsed.entropy_start);
+            if walk.eoi == Some(entropy_end) {
+                NON_BASELINE_SCORE
+            } else {
+                0.0
+            }
+        }
+        Assessment::NonBaseline => NON_BASELINE_SCORE,
+        Assessment::Baseline(scan) => {
+            let progress = scan.decode(&data[parsed.entropy_start..entropy_end]);
+            (progress.decoded as f32 / scan.grid.total() as f32).min(1.0)
+        }
+    }
+}
+
+pub fn check_structure(data: &[u8]) -> Result<(), ArgosError> {
+    let parsed = parse_jpeg(data)?;
+    collect_huffman_luts(&parsed.segments)?;
+    if parsed.entropy_end.is_none() {
+        return Err(ArgosError::Validation {
+            kind: ValidationKind::MissingEoi,
+            offset: data.len() as u64,
+        });
     }
+    Ok(())
+}
+
+fn dimensions(parsed: &ParsedJpeg) -> Option<(u32, u32)> {
+    let sof = parsed.segments.iter().find(|s| is_sof_marker(s.marker))?;
+    let frame = parse_frame(sof.data)?;
+    Some((frame.width as u32, frame.height as u32))
+}
 
-    let entropy = &data[parsed.entropy_start..parsed.entropy_end];
-    let mut bits = BitReader::new(entropy);
-    let mut decoded = 0usize;
+fn quality_estimate(parsed: &ParsedJpeg) -> Option<u8> {
+    let table = parsed
+        .segments
+        .iter()
+        .filter(|s| s.marker == DQT)
+        .find_map(|s| luminance_table(s.data))?;
+    let sum: u32 = table.iter().map(|&q| u32::from(q)).sum();
+    let reference: u32 = STANDARD_LUMINANCE_TABLE.iter().map(|&q| u32::from(q)).sum();
+    let scaling = (sum * 100 + reference / 2) / reference;
+    let quality = if scaling <= 100 {
+        (200 - scaling) / 2
+    } else {
+        5000 / scaling
+    };
+    Some(quality.clamp(1, 100) as u8)
+}
 
-    while decoded < expected_mcus {
-        if decode_mcu(&mut bits, &scan, &dc_luts, &ac_luts).is_none() {
-            break;
+fn luminance_table(body: &[u8]) -> Option<Vec<u16>> {
+    let mut offset = 0;
+    while offset < body.len() {
+        let header = body[offset];
+        let wide = (header >> 4) & 0x0F != 0;
+        let entry_size = if wide { 128 } else { 64 };
+        let entries = body.get(offset + 1..offset + 1 + entry_size)?;
+        if header & 0x0F == 0 {
+            let table = if wide {
+                entries
+                    .chunks_exact(2)
+                    .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                    .collect()
+            } else {
+                entries.iter().map(|&q| u16::from(q)).collect()
+            };
+            return Some(table);
         }
-        decoded += 1;
+        offset += 1 + entry_size;
     }
+    None
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IccProfile {
+    pub declared_segments: u8,
+    pub missing_segments: Vec<u8>,
+    pub bytes: Option<Vec<u8>>,
+}
+
+fn icc_profile(parsed: &ParsedJpeg) -> Option<IccProfile> {
+    let mut declared = 0u8;
+    let mut parts: Vec<(u8, &[u8])> = Vec::new();
+    for seg in parsed.segments.iter().filter(|s| s.marker == APP2) {
+        let Some([sequence, count, payload @ ..]) = seg.data.strip_prefix(ICC_TAG) else {
+            continue;
+        };
+        declared = declared.max(*count);
+        parts.push((*sequence, payload));
+    }
+    if parts.is_empty() {
+        return None;
+    }
+    parts.sort_by_key(|&(sequence, _)| sequence);
+    let missing_segments = (1..=declared)
+        .filter(|n| !parts.iter().any(|&(sequence, _)| sequence == *n))
+        .collect();
+    let complete = parts.iter().map(|&(sequence, _)| sequence).eq(1..=declared);
+    let bytes = complete.then(|| {
+        parts
+            .iter()
+            .flat_map(|&(_, payload)| payload)
+            .copied()
+            .collect()
+    });
+    Some(IccProfile {
+        declared_segments: declared,
+        missing_segments,
+        bytes,
+    })
+}
 
-    Ok((decoded as f32 / expected_mcus as f32).min(1.0))
+#[derive(Debug, Clone)]
+pub struct RestartTruncation {
+    pub bytes: Vec<u8>,
+    pub source_length: usize,
+    pub mcu_rows: usize,
+}
+
+pub fn truncate_to_last_valid_restart(data: &[u8]) -> Option<RestartTruncation> {
+    let parsed = parse_jpeg(data).ok()?;
+    let Assessment::Baseline(scan) = assess(&parsed) else {
+        return None;
+    };
+    if scan.restart_interval == 0 {
+        return None;
+    }
+
+    let entropy_end = parsed.entropy_end.unwrap_or(data.len());
+    let progress = scan.decode(&data[parsed.entropy_start..entropy_end]);
+    if progress.decoded >= scan.grid.total() {
+        return None;
+    }
+
+    let boundary = progress.last_restart?;
+    let mcu_rows = boundary.mcus_before / scan.grid.per_row;
+    if mcu_rows == 0 {
+        return None;
+    }
+
+    let source_length = parsed.entropy_start + boundary.entropy_offset;
+    let height = (mcu_rows * scan.grid.height_px).min(scan.frame.height as usize) as u16;
+    let height_field = scan.sof_start + 5;
+
+    let mut bytes = Vec::with_capacity(source_length + 2);
+    bytes.extend_from_slice(&data[..source_length]);
+    bytes[height_field..height_field + 2].copy_from_slice(&height.to_be_bytes());
+    bytes.extend_from_slice(&[0xFF, EOI]);
+
+    Some(RestartTruncation {
+        bytes,
+        source_length,
+        mcu_rows,
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct ScanTruncation {
+    pub bytes: Vec<u8>,
+    pub source_length: usize,
+    pub complete_scans: usize,
+}
+
+pub fn truncate_to_last_complete_scan(data: &[u8]) -> Option<ScanTruncation> {
+    let parsed = parse_jpeg(data).ok()?;
+    if !matches!(assess(&parsed), Assessment::NonBaseline) || !is_progressive(&parsed) {
+        return None;
+    }
+    let walk = walk_scans(data, parsed.entropy_start);
+    if walk.eoi.is_some() {
+        return None;
+    }
+    let source_length = walk.last_boundary?;
+
+    let mut bytes = Vec::with_capacity(source_length + 2);
+    bytes.extend_from_slice(&data[..source_length]);
+    bytes.extend_from_slice(&[0xFF, EOI]);
+
+    Some(ScanTruncation {
+        bytes,
+        source_length,
+        complete_scans: walk.complete_scans,
+    })
 }
 
 pub fn continuation_score(block: &[u8]) -> f32 {
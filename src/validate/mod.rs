@@ -1,2 +1,19 @@
 pub mod jpeg;
 pub mod png;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StructureStep {
+    Skip(u64),
+    End,
+    NeedMore,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Inspection {
+    pub score: f32,
+    pub dimensions: Option<(u32, u32)>,
+    pub animation: Option<png::Animation>,
+    pub icc_profile: Option<jpeg::IccProfile>,
+    pub quality: Option<u8>,
+    pub idat: Option<png::IdatInflation>,
+}
@@ -1,74 +1,603 @@
 use crc32fast::Hasher;
+use flate2::read::ZlibDecoder;
+use flate2::write::ZlibEncoder;
+use flate2::{Compression, Decompress, FlushDecompress, Status};
+use std::io::{Read, Write};
 
 use crate::error::{ArgosError, ValidationKind};
+use crate::validate::{Inspection, StructureStep};
 
 const SIGNATURE: [u8; 8] = [0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+const IHDR_LEN: usize = 13;
+const INFLATE_WINDOW: usize = 32 * 1024;
+const MAX_REPAIR_SCANLINE: u64 = 16 * 1024 * 1024;
+const MAX_ICC_PROFILE: u64 = 16 * 1024 * 1024;
+const COLOR_CHUNKS: [[u8; 4]; 4] = [*b"cHRM", *b"gAMA", *b"iCCP", *b"sRGB"];
+const ADAM7_PASSES: [(u32, u32, u32, u32); 7] = [
+    (0, 0, 8, 8),
+    (4, 0, 8, 8),
+    (0, 4, 4, 8),
+    (2, 0, 4, 4),
+    (0, 2, 2, 4),
+    (1, 0, 2, 2),
+    (0, 1, 1, 2),
+];
 
 #[derive(Debug, Clone)]
 pub struct Chunk {
+    pub offset: usize,
     pub chunk_type: [u8; 4],
     pub data: Vec<u8>,
     pub crc: u32,
 }
 
 pub fn validate(data: &[u8]) -> Result<f32, ArgosError> {
-    if data.len() < SIGNATURE.len() || data[..SIGNATURE.len()] != SIGNATURE {
-        return Ok(0.0);
+    inspect(data).map(|inspection| inspection.score)
+}
+
+pub fn inspect(data: &[u8]) -> Result<Inspection, ArgosError> {
+    if !data.starts_with(&SIGNATURE) {
+        return Ok(Inspection::default());
     }
 
-    let chunks = match parse_chunks(data) {
-        Ok(c) => c,
-        Err(ArgosError::Validation { .. }) => return Ok(0.0),
+    let (chunks, _) = walk_chunks(data);
+    let ihdr = chunks
+        .first()
+        .filter(|chunk| is_ihdr(&chunk.chunk_type))
+        .and_then(|chunk| Ihdr::parse(&chunk.data));
+    let idat = match check_chunks(data, &chunks) {
+        Ok(()) => ihdr.map(|ihdr| inflate_chunks(&ihdr, &chunks)),
+        Err(ArgosError::Validation { .. }) => None,
         Err(e) => return Err(e),
     };
+    let score = match (ihdr, idat) {
+        (Some(ihdr), Some(idat)) => crc_score(&chunks).min(idat.score(&ihdr)),
+        _ => 0.0,
+    };
 
-    if chunks.is_empty() {
-        return Ok(0.0);
+    Ok(Inspection {
+        score,
+        dimensions: ihdr.map(|ihdr| (ihdr.width, ihdr.height)),
+        animation: animation(&chunks),
+        idat,
+        ..Inspection::default()
+    })
+}
+
+fn crc_score(chunks: &[Chunk]) -> f32 {
+    let valid = chunks.iter().filter(|chunk| verify_crc(chunk)).count();
+    (valid as f32 / chunks.len() as f32).min(1.0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ihdr {
+    pub width: u32,
+    pub height: u32,
+    pub bit_depth: u8,
+    pub color_type: u8,
+    pub interlaced: bool,
+}
+
+impl Ihdr {
+    pub fn parse(data: &[u8]) -> Option<Self> {
+        if data.len() != IHDR_LEN {
+            return None;
+        }
+        let width = u32::from_be_bytes([data[0], data[1], data[2], data[3]]);
+        let height = u32::from_be_bytes([data[4], data[5], data[6], data[7]]);
+        let interlaced = match data[12] {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+        let ihdr = Self {
+            width,
+            height,
+            bit_depth: data[8],
+            color_type: data[9],
+            interlaced,
+        };
+        let valid =
+            width > 0 && height > 0 && data[10] == 0 && data[11] == 0 && ihdr.channels().is_some();
+        valid.then_some(ihdr)
     }
 
-    if !is_ihdr(&chunks[0].chunk_type) {
-        return Ok(0.0);
+    fn channels(&self) -> Option<u64> {
+        match (self.color_type, self.bit_depth) {
+            (0, 1 | 2 | 4 | 8 | 16) => Some(1),
+            (3, 1 | 2 | 4 | 8) => Some(1),
+            (2, 8 | 16) => Some(3),
+            (4, 8 | 16) => Some(2),
+            (6, 8 | 16) => Some(4),
+            _ => None,
+        }
+    }
+
+    fn scanline_bytes(&self, width: u32) -> u64 {
+        let bits = width as u64 * self.channels().unwrap_or(0) * self.bit_depth as u64;
+        1 + bits.div_ceil(8)
+    }
+
+    fn passes(&self) -> Vec<(u64, u64)> {
+        if !self.interlaced {
+            return vec![(self.scanline_bytes(self.width), self.height as u64)];
+        }
+        ADAM7_PASSES
+            .iter()
+            .map(|&(x0, y0, dx, dy)| {
+                let width = self.width.saturating_sub(x0).div_ceil(dx);
+                let rows = self.height.saturating_sub(y0).div_ceil(dy);
+                (width, rows)
+            })
+            .filter(|&(width, rows)| width > 0 && rows > 0)
+            .map(|(width, rows)| (self.scanline_bytes(width), rows as u64))
+            .collect()
+    }
+
+    pub fn raw_length(&self) -> u64 {
+        self.passes().iter().fold(0u64, |total, &(stride, rows)| {
+            total.saturating_add(stride.saturating_mul(rows))
+        })
+    }
+
+    pub fn scanlines(&self) -> u64 {
+        self.passes().iter().map(|&(_, rows)| rows).sum()
+    }
+
+    pub fn complete_scanlines(&self, inflated: u64) -> u64 {
+        let mut remaining = inflated;
+        let mut complete = 0;
+        for (stride, rows) in self.passes() {
+            let pass_bytes = stride.saturating_mul(rows);
+            if remaining < pass_bytes {
+                return complete + remaining / stride;
+            }
+            remaining -= pass_bytes;
+            complete += rows;
+        }
+        complete
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdatFailure {
+    pub chunk_offset: usize,
+    pub scanline: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IdatInflation {
+    pub expected: u64,
+    pub inflated: u64,
+    pub failure: Option<IdatFailure>,
+}
+
+impl IdatInflation {
+    fn score(&self, ihdr: &Ihdr) -> f32 {
+        match self.failure {
+            None => 1.0,
+            Some(failure) => {
+                let total = ihdr.scanlines();
+                failure.scanline.min(total - 1) as f32 / total as f32
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Animation {
+    pub frames: u32,
+    pub duration_ms: u64,
+}
+
+fn animation(chunks: &[Chunk]) -> Option<Animation> {
+    let actl = chunks.iter().find(|chunk| &chunk.chunk_type == b"acTL")?;
+    let frames = u32::from_be_bytes(actl.data.get(..4)?.try_into().ok()?);
+    let duration_ms = chunks
+        .iter()
+        .filter(|chunk| &chunk.chunk_type == b"fcTL")
+        .filter_map(|chunk| frame_delay_ms(&chunk.data))
+        .sum();
+    Some(Animation {
+        frames,
+        duration_ms,
+    })
+}
+
+fn frame_delay_ms(fctl: &[u8]) -> Option<u64> {
+    let delay = fctl.get(20..24)?;
+    let numerator = u64::from(u16::from_be_bytes([delay[0], delay[1]]));
+    let denominator = match u16::from_be_bytes([delay[2], delay[3]]) {
+        0 => 100,
+        d => u64::from(d),
+    };
+    Some(numerator * 1000 / denominator)
+}
+
+pub fn inflate_idat(data: &[u8]) -> Result<IdatInflation, ArgosError> {
+    let chunks = parse_chunks(data)?;
+    let ihdr = Ihdr::parse(&chunks[0].data).ok_or(ArgosError::Validation {
+        kind: ValidationKind::MissingIhdr,
+        offset: chunks[0].offset as u64,
+    })?;
+    Ok(inflate_chunks(&ihdr, &chunks))
+}
+
+fn inflate_chunks(ihdr: &Ihdr, chunks: &[Chunk]) -> IdatInflation {
+    let mut stream = IdatStream::new(ihdr.raw_length());
+    let mut failing_offset = None;
+
+    for chunk in chunks.iter().filter(|c| is_idat(&c.chunk_type)) {
+        if !stream.feed(&chunk.data, &mut |_| {}) {
+            failing_offset = Some(chunk.offset);
+            break;
+        }
+        if stream.ended {
+            break;
+        }
+    }
+
+    let expected = stream.expected;
+    let inflated = stream.inflated().min(expected);
+    if failing_offset.is_none() && stream.ended && stream.inflated() == expected {
+        return IdatInflation {
+            expected,
+            inflated,
+            failure: None,
+        };
+    }
+
+    let chunk_offset = failing_offset.unwrap_or_else(|| {
+        chunks
+            .iter()
+            .rfind(|c| is_idat(&c.chunk_type))
+            .unwrap_or(&chunks[chunks.len() - 1])
+            .offset
+    });
+    IdatInflation {
+        expected,
+        inflated,
+        failure: Some(IdatFailure {
+            chunk_offset,
+            scanline: ihdr.complete_scanlines(inflated),
+        }),
     }
+}
+
+struct IdatStream {
+    inflater: Decompress,
+    window: Vec<u8>,
+    expected: u64,
+    ended: bool,
+}
 
-    if !is_iend(&chunks[chunks.len() - 1].chunk_type) {
-        return Ok(0.0);
+impl IdatStream {
+    fn new(expected: u64) -> Self {
+        Self {
+            inflater: Decompress::new(true),
+            window: vec![0u8; INFLATE_WINDOW],
+            expected,
+            ended: false,
+        }
+    }
+
+    fn inflated(&self) -> u64 {
+        self.inflater.total_out()
+    }
+
+    fn feed(&mut self, mut input: &[u8], sink: &mut impl FnMut(&[u8])) -> bool {
+        loop {
+            let in_before = self.inflater.total_in();
+            let out_before = self.inflater.total_out();
+            let status = self
+                .inflater
+                .decompress(input, &mut self.window, FlushDecompress::None);
+            let consumed = (self.inflater.total_in() - in_before) as usize;
+            let produced = (self.inflater.total_out() - out_before) as usize;
+            input = &input[consumed..];
+            sink(&self.window[..produced]);
+
+            if self.inflater.total_out() > self.expected {
+                return false;
+            }
+            match status {
+                Err(_) => return false,
+                Ok(Status::StreamEnd) => {
+                    self.ended = true;
+                    return true;
+                }
+                Ok(Status::BufError) => return true,
+                Ok(Status::Ok) => {
+                    if input.is_empty() && produced < self.window.len() {
+                        return true;
+                    }
+                    if consumed == 0 && produced == 0 {
+                        return true;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PngRepair {
+    pub bytes: Vec<u8>,
+    pub source_length: usize,
+    pub scanlines: u32,
+    pub dropped: Vec<[u8; 4]>,
+}
+
+struct ScanlineEncoder {
+    stride: usize,
+    limit: u64,
+    rows: u64,
+    pending: Vec<u8>,
+    encoder: ZlibEncoder<Vec<u8>>,
+    failed: bool,
+}
+
+impl ScanlineEncoder {
+    fn new(ihdr: &Ihdr) -> Self {
+        let stride = ihdr.scanline_bytes(ihdr.width) as usize;
+        Self {
+            stride,
+            limit: ihdr.height as u64,
+            rows: 0,
+            pending: Vec::new(),
+            encoder: ZlibEncoder::new(Vec::new(), Compression::default()),
+            failed: false,
+        }
+    }
+
+    fn push(&mut self, mut bytes: &[u8]) {
+        while !bytes.is_empty() && self.rows < self.limit {
+            let take = (self.stride - self.pending.len()).min(bytes.len());
+            self.pending.extend_from_slice(&bytes[..take]);
+            bytes = &bytes[take..];
+            if self.pending.len() == self.stride {
+                self.failed |= self.encoder.write_all(&self.pending).is_err();
+                self.pending.clear();
+                self.rows += 1;
+            }
+        }
+    }
+
+    fn finish(self) -> Option<(u64, Vec<u8>)> {
+        if self.failed {
+            return None;
+        }
+        let compressed = self.encoder.finish().ok()?;
+        Some((self.rows, compressed))
+    }
+}
+
+pub fn repair_truncated(data: &[u8], inspection: &Inspection) -> Option<PngRepair> {
+    if !data.starts_with(&SIGNATURE) || inspection.score >= 1.0 {
+        return None;
+    }
+    let (chunks, tail) = walk_chunks(data);
+    let ihdr_chunk = chunks
+        .first()
+        .filter(|c| is_ihdr(&c.chunk_type) && verify_crc(c))?;
+    let ihdr = Ihdr::parse(&ihdr_chunk.data)
+        .filter(|i| !i.interlaced && i.scanline_bytes(i.width) <= MAX_REPAIR_SCANLINE)?;
+
+    let first_idat = chunks.iter().position(|c| is_idat(&c.chunk_type));
+    let header_end = first_idat.unwrap_or(chunks.len());
+    let mut header = Vec::new();
+    let mut dropped = Vec::new();
+    for chunk in &chunks[1..header_end] {
+        if is_iend(&chunk.chunk_type) {
+            return None;
+        }
+        match (salvageable(chunk), is_critical(&chunk.chunk_type)) {
+            (true, _) => header.push(chunk),
+            (false, true) => return None,
+            (false, false) => dropped.push(chunk.chunk_type),
+        }
+    }
+    header.sort_by_key(|chunk| !COLOR_CHUNKS.contains(&chunk.chunk_type));
+    dropped.extend(
+        chunks[header_end..]
+            .iter()
+            .filter(|c| !is_critical(&c.chunk_type))
+            .map(|c| c.chunk_type),
+    );
+
+    let mut stream = IdatStream::new(ihdr.raw_length());
+    let mut rows = ScanlineEncoder::new(&ihdr);
+    let mut source_length = ihdr_chunk.offset + 12 + ihdr_chunk.data.len();
+    let mut intact = true;
+    for chunk in &chunks[header_end..] {
+        if !is_idat(&chunk.chunk_type) || !verify_crc(chunk) {
+            intact = false;
+            break;
+        }
+        let fed = stream.feed(&chunk.data, &mut |bytes| rows.push(bytes));
+        source_length = chunk.offset + 12 + chunk.data.len();
+        if !fed || stream.ended {
+            intact = false;
+            break;
+        }
+    }
+    let partial = tail.filter(|t| intact && is_idat(&t.chunk_type));
+    if let Some(partial) = partial {
+        stream.feed(&partial.data, &mut |bytes| rows.push(bytes));
+        source_length = data.len();
     }
 
-    let mut valid = 0usize;
+    let (scanlines, compressed) = rows.finish()?;
+    if scanlines == 0 {
+        return None;
+    }
+    let scanlines = scanlines as u32;
+
+    let mut repaired_ihdr = ihdr_chunk.data.clone();
+    repaired_ihdr[4..8].copy_from_slice(&scanlines.to_be_bytes());
+
+    let mut bytes = Vec::with_capacity(compressed.len() + 1024);
+    bytes.extend_from_slice(&SIGNATURE);
+    write_chunk(&mut bytes, b"IHDR", &repaired_ihdr);
+    for chunk in header {
+        write_chunk(&mut bytes, &chunk.chunk_type, &chunk.data);
+    }
+    write_chunk(&mut bytes, b"IDAT", &compressed);
+    write_chunk(&mut bytes, b"IEND", &[]);
+
+    Some(PngRepair {
+        bytes,
+        source_length,
+        scanlines,
+        dropped,
+    })
+}
+
+#[derive(Debug, Clone)]
+pub struct CrcRepair {
+    pub bytes: Vec<u8>,
+    pub chunks_repaired: usize,
+    pub dropped: Vec<[u8; 4]>,
+}
+
+pub fn repair_crc(data: &[u8], inspection: &Inspection) -> Option<CrcRepair> {
+    let inflated = inspection.idat.is_some_and(|idat| idat.failure.is_none());
+    if !data.starts_with(&SIGNATURE) || !inflated {
+        return None;
+    }
+    let chunks = parse_chunks(data).ok()?;
+
+    let mut bytes = Vec::with_capacity(data.len());
+    bytes.extend_from_slice(&SIGNATURE);
+    let mut chunks_repaired = 0;
+    let mut dropped = Vec::new();
     for chunk in &chunks {
-        if verify_crc(chunk) {
-            valid += 1;
+        if !verify_crc(chunk) {
+            if is_critical(&chunk.chunk_type) {
+                if !critical_chunk_plausible(chunk) {
+                    return None;
+                }
+            } else if !salvageable(chunk) {
+                dropped.push(chunk.chunk_type);
+                continue;
+            }
+            chunks_repaired += 1;
         }
+        write_chunk(&mut bytes, &chunk.chunk_type, &chunk.data);
     }
 
-    let score = if chunks.is_empty() {
-        0.0
-    } else {
-        (valid as f32 / chunks.len() as f32).min(1.0)
+    (chunks_repaired + dropped.len() > 0).then_some(CrcRepair {
+        bytes,
+        chunks_repaired,
+        dropped,
+    })
+}
+
+fn salvageable(chunk: &Chunk) -> bool {
+    verify_crc(chunk) || (&chunk.chunk_type == b"iCCP" && icc_profile_intact(&chunk.data))
+}
+
+fn icc_profile_intact(data: &[u8]) -> bool {
+    let Some(name_end) = data.iter().position(|&b| b == 0) else {
+        return false;
     };
+    if !(1..=79).contains(&name_end) || data.get(name_end + 1) != Some(&0) {
+        return false;
+    }
+    let stream = &data[name_end + 2..];
+    let mut decoder = ZlibDecoder::new(stream);
+    let mut profile = Vec::new();
+    if (&mut decoder)
+        .take(MAX_ICC_PROFILE)
+        .read_to_end(&mut profile)
+        .is_err()
+        || decoder.total_in() != stream.len() as u64
+    {
+        return false;
+    }
+    profile.len() >= 128
+        && profile[..4] == (profile.len() as u32).to_be_bytes()
+        && &profile[36..40] == b"acsp"
+}
 
-    Ok(score)
+fn critical_chunk_plausible(chunk: &Chunk) -> bool {
+    match &chunk.chunk_type {
+        b"IHDR" | b"IDAT" => true,
+        b"PLTE" => chunk.data.len() % 3 == 0 && (3..=768).contains(&chunk.data.len()),
+        b"IEND" => chunk.data.is_empty(),
+        _ => false,
+    }
+}
+
+fn write_chunk(out: &mut Vec<u8>, chunk_type: &[u8; 4], data: &[u8]) {
+    let mut hasher = Hasher::new();
+    hasher.update(chunk_type);
+    hasher.update(data);
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(chunk_type);
+    out.extend_from_slice(data);
+    out.extend_from_slice(&hasher.finalize().to_be_bytes());
 }
 
 pub fn parse_chunks(data: &[u8]) -> Result<Vec<Chunk>, ArgosError> {
+    let (chunks, _) = walk_chunks(data);
+    check_chunks(data, &chunks)?;
+    Ok(chunks)
+}
+
+fn check_chunks(data: &[u8], chunks: &[Chunk]) -> Result<(), ArgosError> {
     if data.len() < SIGNATURE.len() + 12 {
         return Err(ArgosError::Validation {
             kind: ValidationKind::TruncatedChunk,
+            offset: 0,
+        });
+    }
+
+    if chunks.is_empty() {
+        return Err(ArgosError::Validation {
+            kind: ValidationKind::TruncatedChunk,
+            offset: SIGNATURE.len() as u64,
+        });
+    }
+
+    if !is_ihdr(&chunks[0].chunk_type) {
+        return Err(ArgosError::Validation {
+            kind: ValidationKind::MissingIhdr,
+            offset: chunks[0].offset as u64,
         });
     }
 
+    let last = &chunks[chunks.len() - 1];
+    if !is_iend(&last.chunk_type) {
+        return Err(ArgosError::Validation {
+            kind: ValidationKind::MissingIend,
+            offset: (last.offset + 12 + last.data.len()) as u64,
+        });
+    }
+
+    Ok(())
+}
+
+fn walk_chunks(data: &[u8]) -> (Vec<Chunk>, Option<Chunk>) {
     let mut chunks = Vec::new();
     let mut pos = SIGNATURE.len();
 
     while pos + 12 <= data.len() {
         let len =
             u32::from_be_bytes([data[pos], data[pos + 1], data[pos + 2], data[pos + 3]]) as usize;
+        let chunk_type = [data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]];
 
         if pos + 12 + len > data.len() {
-            break;
+            let available = data.len().min(pos + 8 + len);
+            let tail = Chunk {
+                offset: pos,
+                chunk_type,
+                data: data[pos + 8..available].to_vec(),
+                crc: 0,
+            };
+            return (chunks, Some(tail));
         }
 
-        let chunk_type = [data[pos + 4], data[pos + 5], data[pos + 6], data[pos + 7]];
         let chunk_data = data[pos + 8..pos + 8 + len].to_vec();
         let crc = u32::from_be_bytes([
             data[pos + 8 + len],
@@ -78,6 +607,7 @@ pub fn parse_chunks(data: &[u8]) -> Result<Vec<Chunk>, ArgosError> {
         ]);
 
         chunks.push(Chunk {
+            offset: pos,
             chunk_type,
             data: chunk_data,
             crc,
@@ -90,25 +620,7 @@ pub fn parse_chunks(data: &[u8]) -> Result<Vec<Chunk>, ArgosError> {
         pos += 12 + len;
     }
 
-    if chunks.is_empty() {
-        return Err(ArgosError::Validation {
-            kind: ValidationKind::TruncatedChunk,
-        });
-    }
-
-    if !is_ihdr(&chunks[0].chunk_type) {
-        return Err(ArgosError::Validation {
-            kind: ValidationKind::MissingIhdr,
-        });
-    }
-
-    if !is_iend(&chunks[chunks.len() - 1].chunk_type) {
-        return Err(ArgosError::Validation {
-            kind: ValidationKind::MissingIend,
-        });
-    }
-
-    Ok(chunks)
+    (chunks, None)
 }
 
 fn verify_crc(chunk: &Chunk) -> bool {
@@ -126,52 +638,153 @@ fn is_iend(t: &[u8; 4]) -> bool {
     t == b"IEND"
 }
 
+fn is_idat(t: &[u8; 4]) -> bool {
+    t == b"IDAT"
+}
+
+fn is_critical(t: &[u8; 4]) -> bool {
+    t[0].is_ascii_uppercase()
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum ChunkStage {
+    #[default]
+    Header,
+    Body,
+    Crc,
+    Ended,
+    Broken,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct PartialChunk {
-    pub pending: Vec<u8>,
-    pub chunk_type: [u8; 4],
-    pub expected_len: u32,
+    stage: ChunkStage,
+    pending: Vec<u8>,
+    chunk_type: [u8; 4],
+    expected_len: u32,
+    consumed: usize,
+    crc: u32,
 }
 
-pub fn continuation_score(partial: &mut PartialChunk, block: &[u8]) -> f32 {
-    partial.pending.extend_from_slice(block);
+impl PartialChunk {
+    pub fn after_signature(block: &[u8]) -> Self {
+        let mut partial = Self::default();
+        if let Some(pos) = block.windows(SIGNATURE.len()).position(|w| w == SIGNATURE) {
+            partial.feed(&block[pos + SIGNATURE.len()..]);
+        }
+        partial
+    }
 
-    if partial.pending.len() < 8 {
-        return 0.5;
+    fn feed(&mut self, mut bytes: &[u8]) -> usize {
+        let mut verified = 0;
+        while !bytes.is_empty() {
+            match self.stage {
+                ChunkStage::Header => {
+                    let take = (8 - self.pending.len()).min(bytes.len());
+                    self.pending.extend_from_slice(&bytes[..take]);
+                    bytes = &bytes[take..];
+                    if self.pending.len() == 8 {
+                        self.begin_chunk();
+                    }
+                }
+                ChunkStage::Body => {
+                    let remaining = self.expected_len as usize + 8 - self.consumed;
+                    let take = remaining.min(bytes.len());
+                    let mut hasher = Hasher::new_with_initial(self.crc);
+                    hasher.update(&bytes[..take]);
+                    self.crc = hasher.finalize();
+                    self.consumed += take;
+                    bytes = &bytes[take..];
+                    if take == remaining {
+                        self.stage = ChunkStage::Crc;
+                    }
+                }
+                ChunkStage::Crc => {
+                    let take = (4 - self.pending.len()).min(bytes.len());
+                    self.pending.extend_from_slice(&bytes[..take]);
+                    self.consumed += take;
+                    bytes = &bytes[take..];
+                    if self.pending.len() == 4 {
+                        let stored = u32::from_be_bytes([
+                            self.pending[0],
+                            self.pending[1],
+                            self.pending[2],
+                            self.pending[3],
+                        ]);
+                        self.pending.clear();
+                        if stored != self.crc {
+                            self.stage = ChunkStage::Broken;
+                            return verified;
+                        }
+                        verified += 1;
+                        self.stage = if is_iend(&self.chunk_type) {
+                            ChunkStage::Ended
+                        } else {
+                            ChunkStage::Header
+                        };
+                        self.consumed = 0;
+                    }
+                }
+                ChunkStage::Ended | ChunkStage::Broken => break,
+            }
+        }
+        verified
     }
 
-    if partial.expected_len == 0 && partial.pending.len() >= 8 {
-        partial.expected_len = u32::from_be_bytes([
-            partial.pending[0],
-            partial.pending[1],
-            partial.pending[2],
-            partial.pending[3],
-        ]);
-        partial.chunk_type = [
-            partial.pending[4],
-            partial.pending[5],
-            partial.pending[6],
-            partial.pending[7],
-        ];
+    fn begin_chunk(&mut self) {
+        let header = std::mem::take(&mut self.pending);
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+        let chunk_type = [header[4], header[5], header[6], header[7]];
+        if !plausible_chunk_header(len, &chunk_type) {
+            self.stage = ChunkStage::Broken;
+            return;
+        }
+        let mut hasher = Hasher::new();
+        hasher.update(&chunk_type);
+        self.crc = hasher.finalize();
+        self.chunk_type = chunk_type;
+        self.expected_len = len;
+        self.consumed = 8;
+        self.stage = if len == 0 {
+            ChunkStage::Crc
+        } else {
+            ChunkStage::Body
+        };
     }
 
-    let total_needed = 12 + partial.expected_len as usize;
-    if partial.pending.len() < total_needed {
-        return 0.5 + 0.5 * (partial.pending.len() as f32 / total_needed as f32);
+    fn completion(&self) -> f32 {
+        match self.stage {
+            ChunkStage::Body | ChunkStage::Crc => {
+                self.consumed as f32 / (self.expected_len as f32 + 12.0)
+            }
+            ChunkStage::Header | ChunkStage::Ended | ChunkStage::Broken => 0.0,
+        }
     }
+}
 
-    let data = &partial.pending[8..8 + partial.expected_len as usize];
-    let stored_crc = u32::from_be_bytes([
-        partial.pending[8 + partial.expected_len as usize],
-        partial.pending[9 + partial.expected_len as usize],
-        partial.pending[10 + partial.expected_len as usize],
-        partial.pending[11 + partial.expected_len as usize],
-    ]);
+pub fn chunk_step(bytes: &[u8]) -> StructureStep {
+    let Some(header) = bytes.get(..8) else {
+        return StructureStep::NeedMore;
+    };
+    let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+    let chunk_type = [header[4], header[5], header[6], header[7]];
+    if !plausible_chunk_header(len, &chunk_type) || is_iend(&chunk_type) {
+        return StructureStep::End;
+    }
+    StructureStep::Skip(12 + u64::from(len))
+}
 
-    let mut hasher = Hasher::new();
-    hasher.update(&partial.chunk_type);
-    hasher.update(data);
-    let computed_crc = hasher.finalize();
+fn plausible_chunk_header(len: u32, chunk_type: &[u8; 4]) -> bool {
+    len <= i32::MAX as u32
+        && chunk_type.iter().all(u8::is_ascii_alphabetic)
+        && chunk_type[2].is_ascii_uppercase()
+}
 
-    if computed_crc == stored_crc { 1.0 } else { 0.0 }
+pub fn continuation_score(partial: &mut PartialChunk, block: &[u8]) -> f32 {
+    let verified = partial.feed(block);
+    match partial.stage {
+        ChunkStage::Broken => 0.0,
+        _ if verified > 0 => 1.0,
+        _ => 0.5 + 0.5 * partial.completion(),
+    }
 }
@@ -1,17 +1,23 @@
 mod common;
 
 use argos::carve::ImageFormat;
+use argos::carve::hdd::cluster;
 use argos::carve::hdd::pup::{self, Seed};
 use argos::carve::hdd::sht::{self, Decision, SprtAccumulator};
+use argos::carve::matcher::{Match, Matcher};
 use argos::carve::ssd::Scanner;
+use argos::error::{ArgosError, ValidationKind};
 use argos::validate::{jpeg, png};
 use proptest::prelude::*;
 
 use common::{
-    JPEG_EOI, JPEG_SOI, MARKER_DHT, MARKER_DQT, MARKER_SOF0, MARKER_SOS, PNG_SIGNATURE,
-    baseline_jpeg_with_nonzero_huffman_selectors, baseline_jpeg_with_stuffed_entropy,
-    minimal_baseline_jpeg, multi_block_baseline_jpeg, png_chunk, progressive_jpeg, segment,
-    single_symbol_dht, valid_png,
+    JPEG_EOI, JPEG_SOI, MARKER_APP1, MARKER_DHT, MARKER_DQT, MARKER_SOF0, MARKER_SOS,
+    PNG_SIGNATURE, baseline_jpeg_with_nonzero_huffman_selectors,
+    baseline_jpeg_with_stuffed_entropy, dc_only_jpeg, jpeg_with_exif_thumbnail,
+    jpeg_with_icc_segments, minimal_baseline_jpeg, multi_block_baseline_jpeg, png_chunk, png_ihdr,
+    png_with_idat_chunks, progressive_jpeg, progressive_jpeg_with_scans,
+    restart_jpeg_with_corrupted_tail, restart_marker_offsets, rgb_png, rgb_scanlines, segment,
+    single_symbol_dht, valid_png, zlib_compress,
 };
 
 const BLOCK_SIZE: usize = 4096;
@@ -27,6 +33,33 @@ fn scan_full(data: &[u8]) -> Vec<argos::carve::Candidate> {
     scanner.scan_block(data).expect("scan")
 }
 
+fn match_sliced(matcher: &Matcher, data: &[u8], chunk: usize) -> Vec<Match> {
+    let mut out = Vec::new();
+    let mut pos = 0;
+    while pos < data.len() {
+        let start = pos.saturating_sub(matcher.overlap());
+        let end = (pos + chunk).min(data.len());
+        out.extend(matcher.find(&data[start..end], pos - start, start as u64));
+        pos = end;
+    }
+    out
+}
+
+fn match_carried(matcher: &Matcher, data: &[u8], chunk: usize) -> Vec<Match> {
+    let mut out = Vec::new();
+    let mut carried: Vec<u8> = Vec::new();
+    let mut base = 0u64;
+    for block in data.chunks(chunk) {
+        let mut haystack = carried.clone();
+        haystack.extend_from_slice(block);
+        out.extend(matcher.find(&haystack, carried.len(), base - carried.len() as u64));
+        let keep = matcher.overlap().min(haystack.len());
+        carried = haystack[haystack.len() - keep..].to_vec();
+        base += block.len() as u64;
+    }
+    out
+}
+
 fn scan_split(data: &[u8], boundary: usize) -> Vec<argos::carve::Candidate> {
     let mut scanner = Scanner::new().expect("scanner");
     let mut out = Vec::new();
@@ -36,6 +69,37 @@ fn scan_split(data: &[u8], boundary: usize) -> Vec<argos::carve::Candidate> {
     out
 }
 
+#[test]
+fn scanner_skip_to_keeps_absolute_offsets_across_read_gaps() {
+    let payload = [0xFF, 0xD8, 0xAA, 0xBB, 0xFF, 0xD9];
+    let first = vec![0xABu8; 512];
+    let mut second = vec![0xABu8; 512];
+    second[100..106].copy_from_slice(&payload);
+
+    let mut scanner = Scanner::new().expect("scanner");
+    assert!(scanner.scan_block(&first).expect("first").is_empty());
+    scanner.skip_to(1024);
+    let found = scanner.scan_block(&second).expect("second");
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].offset, 1124);
+    assert_eq!(found[0].length, Some(6));
+}
+
+#[test]
+fn scanner_skip_to_drops_overlap_so_patterns_do_not_span_gaps() {
+    let mut first = vec![0xABu8; 512];
+    first[511] = 0xFF;
+    let mut second = vec![0xABu8; 512];
+    second[0] = 0xD8;
+    second[10] = 0xFF;
+    second[11] = 0xD9;
+
+    let mut scanner = Scanner::new().expect("scanner");
+    scanner.scan_block(&first).expect("first");
+    scanner.skip_to(1024);
+    assert!(scanner.scan_block(&second).expect("second").is_empty());
+}
+
 #[test]
 fn aho_corasick_locates_synthesized_jpeg_at_known_offset() {
     let mut buffer = vec![0xABu8; 200];
@@ -186,6 +250,228 @@ fn aho_corasick_nested_headers_do_not_emit_overlapping_artifacts() {
     }
 }
 
+#[test]
+fn truncated_jpeg_header_does_not_swallow_the_intact_files_after_it() {
+    let jpeg = minimal_baseline_jpeg();
+    let mut data = vec![
+        0xFF, 0xD8, 0xFF, 0xE0, 0x00, 0x10, b'J', b'F', b'I', b'F', 0x00,
+    ];
+    data.extend_from_slice(&[0x5A; 64]);
+    let mut offsets = Vec::new();
+    for _ in 0..5 {
+        offsets.push(data.len() as u64);
+        data.extend_from_slice(&jpeg);
+        data.extend_from_slice(&[0x5A; 32]);
+    }
+
+    let mut scanner = Scanner::new().expect("scanner");
+    let cands = scanner.scan_block(&data).expect("scan");
+
+    let found: Vec<(u64, Option<u64>)> = cands.iter().map(|c| (c.offset, c.length)).collect();
+    let expected: Vec<(u64, Option<u64>)> = offsets
+        .iter()
+        .map(|&offset| (offset, Some(jpeg.len() as u64)))
+        .collect();
+    assert_eq!(found, expected);
+    assert_eq!(scanner.nested_suppressed(), 0);
+}
+
+const STANDARD_LUMINANCE: [u16; 64] = [
+    16, 11, 10, 16, 24, 40, 51, 61, 12, 12, 14, 19, 26, 58, 60, 55, 14, 13, 16, 24, 40, 57, 69, 56,
+    14, 17, 22, 29, 51, 87, 80, 62, 18, 22, 37, 56, 68, 109, 103, 77, 24, 35, 55, 64, 81, 104, 113,
+    92, 49, 64, 78, 87, 103, 121, 120, 101, 72, 92, 95, 98, 112, 100, 103, 99,
+];
+
+fn libjpeg_table(quality: u32, wide: bool, table_id: u8) -> Vec<u8> {
+    let scale = if quality < 50 {
+        5000 / quality
+    } else {
+        200 - quality * 2
+    };
+    let mut body = vec![(u8::from(wide) << 4) | table_id];
+    for &q in &STANDARD_LUMINANCE {
+        let scaled = ((u32::from(q) * scale + 50) / 100).clamp(1, if wide { 32_767 } else { 255 });
+        if wide {
+            body.extend_from_slice(&(scaled as u16).to_be_bytes());
+        } else {
+            body.push(scaled as u8);
+        }
+    }
+    body
+}
+
+fn jpeg_with_dqt(dqt: &[u8]) -> Vec<u8> {
+    let jpeg = minimal_baseline_jpeg();
+    let mut data = jpeg[..2].to_vec();
+    data.extend_from_slice(&segment(MARKER_DQT, dqt));
+    data.extend_from_slice(&jpeg[2 + 4 + 65..]);
+    data
+}
+
+#[test]
+fn jpeg_quality_estimate_matches_libjpeg_scaling() {
+    for quality in [25, 50, 75, 90] {
+        let data = jpeg_with_dqt(&libjpeg_table(quality, false, 0));
+        let estimate = jpeg::inspect(&data)
+            .expect("inspect")
+            .quality
+            .expect("estimate");
+        assert!(
+            (i32::from(estimate) - quality as i32).abs() <= 1,
+            "quality {quality}: estimated {estimate}"
+        );
+    }
+    let minimal = jpeg::inspect(&minimal_baseline_jpeg()).expect("inspect");
+    assert!(minimal.quality >= Some(99));
+}
+
+#[test]
+fn jpeg_quality_estimate_reads_wide_tables_and_skips_chroma() {
+    let mut dqt = libjpeg_table(95, false, 1);
+    dqt.extend_from_slice(&libjpeg_table(5, true, 0));
+    let inspection = jpeg::inspect(&jpeg_with_dqt(&dqt)).expect("inspect");
+    let estimate = inspection.quality.expect("estimate");
+    assert!((4..=6).contains(&estimate), "estimated {estimate}");
+}
+
+#[test]
+fn jpeg_icc_profile_is_reassembled_in_sequence_order() {
+    let data = jpeg_with_icc_segments(&[(2, b"BBBB"), (1, b"AAAA"), (3, b"CC")], 3);
+    let inspection = jpeg::inspect(&data).expect("inspect");
+    let icc = inspection.icc_profile.expect("icc");
+    assert_eq!(icc.declared_segments, 3);
+    assert!(icc.missing_segments.is_empty());
+    assert_eq!(icc.bytes.as_deref(), Some(&b"AAAABBBBCC"[..]));
+    assert_eq!(inspection.score, 1.0);
+}
+
+#[test]
+fn jpeg_icc_profile_reports_missing_segments() {
+    let data = jpeg_with_icc_segments(&[(1, b"AAAA"), (3, b"CC")], 3);
+    let icc = jpeg::inspect(&data)
+        .expect("inspect")
+        .icc_profile
+        .expect("icc");
+    assert_eq!(icc.missing_segments, vec![2]);
+    assert!(icc.bytes.is_none());
+
+    let duplicated = jpeg_with_icc_segments(&[(1, b"AAAA"), (1, b"AAAA")], 1);
+    let duplicated = jpeg::inspect(&duplicated).expect("inspect");
+    assert!(duplicated.icc_profile.expect("icc").bytes.is_none());
+    let plain = jpeg::inspect(&minimal_baseline_jpeg()).expect("inspect");
+    assert!(plain.icc_profile.is_none());
+}
+
+#[test]
+fn scanner_suppresses_embedded_thumbnail_and_keeps_outer_jpeg_whole() {
+    let jpeg = jpeg_with_exif_thumbnail();
+    let mut device = vec![0xABu8; 64];
+    device.extend_from_slice(&jpeg);
+    device.extend(std::iter::repeat_n(0xABu8, 64));
+
+    for boundary in [1, 64, 80, device.len() / 2, device.len() - 1] {
+        let mut scanner = Scanner::new().expect("scanner");
+        let (a, b) = device.split_at(boundary);
+        let mut cands = scanner.scan_block(a).expect("first");
+        cands.extend(scanner.scan_block(b).expect("second"));
+        assert_eq!(cands.len(), 1, "split at {boundary}");
+        assert_eq!(cands[0].offset, 64);
+        assert_eq!(cands[0].length, Some(jpeg.len() as u64));
+        assert_eq!(scanner.nested_suppressed(), 1);
+    }
+}
+
+#[test]
+fn scanner_keeps_png_nested_inside_jpeg() {
+    let png = valid_png();
+    let mut app = b"Exif\0\0".to_vec();
+    app.extend_from_slice(&png);
+    let outer = minimal_baseline_jpeg();
+    let mut data = outer[..2].to_vec();
+    data.extend_from_slice(&segment(MARKER_APP1, &app));
+    data.extend_from_slice(&outer[2..]);
+
+    let mut scanner = Scanner::new().expect("scanner");
+    let cands = scanner.scan_block(&data).expect("scan");
+    assert_eq!(cands.len(), 2);
+    assert!(
+        cands
+            .iter()
+            .any(|c| c.format == ImageFormat::Png && c.offset == 12)
+    );
+    assert!(
+        cands
+            .iter()
+            .any(|c| c.format == ImageFormat::Jpeg && c.length == Some(data.len() as u64))
+    );
+    assert_eq!(scanner.nested_suppressed(), 0);
+}
+
+#[test]
+fn scanner_skips_zero_blocks_without_losing_a_straddling_footer() {
+    let png = valid_png();
+    let iend_length = png.len() - 12;
+    let (head, tail) = png.split_at(iend_length + 2);
+    let mut scanner = Scanner::new().expect("scanner");
+    let mut cands = scanner.scan_block(head).expect("head");
+    cands.extend(scanner.scan_block(&[0, 0]).expect("zeros"));
+    cands.extend(scanner.scan_block(&tail[2..]).expect("tail"));
+    assert_eq!(cands.len(), 1);
+    assert_eq!(cands[0].length, Some(png.len() as u64));
+
+    let jpeg = minimal_baseline_jpeg();
+    let (head, tail) = jpeg.split_at(jpeg.len() - 2);
+    let zeros = vec![0u8; 64 * 1024];
+    let mut scanner = Scanner::new().expect("scanner");
+    let mut cands = scanner.scan_block(head).expect("head");
+    cands.extend(scanner.scan_block(&zeros).expect("zeros"));
+    cands.extend(scanner.scan_block(tail).expect("tail"));
+    assert_eq!(cands.len(), 1);
+    assert_eq!(cands[0].length, Some((jpeg.len() + zeros.len()) as u64));
+    assert_eq!(scanner.zero_bytes_skipped(), zeros.len() as u64 - 11);
+}
+
+#[test]
+fn structural_errors_carry_the_offset_where_parsing_stopped() {
+    let jpeg = minimal_baseline_jpeg();
+    let mut dht = single_symbol_dht(0);
+    dht[0] = 0x05;
+    let mut bad_table = jpeg[..2].to_vec();
+    bad_table.extend_from_slice(&segment(MARKER_DHT, &dht));
+    bad_table.extend_from_slice(&jpeg[2..]);
+    let cases = [
+        (
+            jpeg::check_structure(&bad_table),
+            ValidationKind::BadHuffmanTable,
+            6,
+        ),
+        (
+            jpeg::check_structure(&jpeg[..jpeg.len() - 2]),
+            ValidationKind::MissingEoi,
+            jpeg.len() as u64 - 2,
+        ),
+    ];
+    for (result, expected_kind, expected_offset) in cases {
+        match result {
+            Err(ArgosError::Validation { kind, offset }) => {
+                assert_eq!((kind, offset), (expected_kind, expected_offset));
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    let png = valid_png();
+    let truncated = &png[..png.len() - 12];
+    match png::parse_chunks(truncated) {
+        Err(ArgosError::Validation { kind, offset }) => {
+            assert_eq!(kind, ValidationKind::MissingIend);
+            assert_eq!(offset, truncated.len() as u64);
+        }
+        other => panic!("unexpected result: {other:?}"),
+    }
+    assert!(jpeg::check_structure(&jpeg).is_ok());
+}
+
 #[test]
 fn aho_corasick_ignores_orphan_footer() {
     let payload = [0u8, 0u8, 0xFF, 0xD9, 0u8];
@@ -198,6 +484,40 @@ proptest! {
     fn aho_corasick_never_panics_on_arbitrary_input(data: Vec<u8>) {
         let _ = scan_full(&data);
     }
+
+    #[test]
+    fn matcher_yields_the_same_matches_for_sliced_and_carried_chunks(
+        data in prop::collection::vec(
+            prop_oneof![Just(0xFFu8), Just(0xD8), Just(0xD9), Just(0x00), any::<u8>()],
+            0..512,
+        ),
+        chunk in 1usize..64,
+    ) {
+        let matcher = Matcher::new().expect("matcher");
+        let whole: Vec<Match> = matcher.find(&data, 0, 0).collect();
+        prop_assert_eq!(&match_sliced(&matcher, &data, chunk), &whole);
+        prop_assert_eq!(&match_carried(&matcher, &data, chunk), &whole);
+    }
+
+    #[test]
+    fn scanner_matches_a_file_exactly_once_near_a_block_boundary(
+        delta in -8192i64..8192,
+        is_png: bool,
+    ) {
+        let file = if is_png { valid_png() } else { minimal_baseline_jpeg() };
+        let chunk = 16 * 1024;
+        let offset = (chunk as i64 + delta) as usize;
+        let mut data = vec![0xABu8; chunk * 3];
+        data[offset..offset + file.len()].copy_from_slice(&file);
+        let mut scanner = Scanner::new().expect("scanner");
+        let mut cands = Vec::new();
+        for block in data.chunks(chunk) {
+            cands.extend(scanner.scan_block(block).expect("scan"));
+        }
+        prop_assert_eq!(cands.len(), 1);
+        prop_assert_eq!(cands[0].offset, offset as u64);
+        prop_assert_eq!(cands[0].length, Some(file.len() as u64));
+    }
 }
 
 #[test]
@@ -410,6 +730,92 @@ fn pup_entropy_like_blocks_do_not_end_candidate_before_footer() {
     );
 }
 
+fn multi_block_png(chunk_len: usize) -> Vec<u8> {
+    let stride = 1 + 64 * 3;
+    let mut state = 0x2545_F491u32;
+    let mut raw = vec![0x00u8; 64 * stride];
+    for (index, byte) in raw.iter_mut().enumerate() {
+        if index % stride == 0 {
+            continue;
+        }
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        *byte = state as u8;
+    }
+    let compressed = zlib_compress(&raw);
+    let chunks: Vec<&[u8]> = compressed.chunks(chunk_len).collect();
+    png_with_idat_chunks(&png_ihdr(64, 64, 2, false), &chunks)
+}
+
+#[test]
+fn pup_reconstructs_contiguous_multiblock_png_until_iend() {
+    let png = multi_block_png(256);
+    assert!(png.len() > BLOCK_SIZE * 2);
+    let mut data = vec![0x00u8; BLOCK_SIZE * 8];
+    let start = BLOCK_SIZE;
+    data[start..start + png.len()].copy_from_slice(&png);
+    let seeds = vec![Seed {
+        block_index: 1,
+        format: ImageFormat::Png,
+    }];
+
+    let cands = pup::run(&seeds, &data, BLOCK_SIZE, 10_000);
+    let recovered = cands
+        .iter()
+        .find(|cand| cand.offset == start as u64 && cand.length == Some(png.len() as u64))
+        .expect("contiguous multi-block PNG candidate");
+    let score = png::validate(bytes_for_candidate(&data, recovered)).expect("validate");
+    assert_eq!(score, 1.0);
+}
+
+#[test]
+fn pup_fragmented_png_stops_at_first_broken_chunk_boundary() {
+    let png = multi_block_png(256);
+    let mut data = vec![0xABu8; BLOCK_SIZE * 8];
+    data[BLOCK_SIZE..BLOCK_SIZE * 2].copy_from_slice(&png[..BLOCK_SIZE]);
+    let rest = &png[BLOCK_SIZE..];
+    data[BLOCK_SIZE * 3..BLOCK_SIZE * 3 + rest.len()].copy_from_slice(rest);
+    let seeds = vec![Seed {
+        block_index: 1,
+        format: ImageFormat::Png,
+    }];
+
+    let cands = pup::run(&seeds, &data, BLOCK_SIZE, 10_000);
+    assert!(!cands.is_empty());
+    for cand in &cands {
+        let start = cand.offset / BLOCK_SIZE as u64;
+        let span = cand.length.expect("length") / BLOCK_SIZE as u64;
+        assert!(
+            !(start <= 2 && 2 < start + span),
+            "gap block was claimed by candidate {cand:?}"
+        );
+    }
+}
+
+#[test]
+fn png_continuation_score_walks_chunk_boundaries_across_blocks() {
+    let png = multi_block_png(256);
+    let mut partial = png::PartialChunk::after_signature(&png[..BLOCK_SIZE]);
+    let mut shadow = partial.clone();
+    assert_eq!(
+        png::continuation_score(&mut partial, &png[BLOCK_SIZE..BLOCK_SIZE * 2]),
+        1.0
+    );
+    assert_eq!(
+        png::continuation_score(&mut shadow, &[0xABu8; BLOCK_SIZE]),
+        0.0
+    );
+}
+
+#[test]
+fn png_continuation_score_stays_neutral_inside_a_large_chunk() {
+    let png = multi_block_png(BLOCK_SIZE * 4);
+    let mut partial = png::PartialChunk::after_signature(&png[..BLOCK_SIZE]);
+    let score = png::continuation_score(&mut partial, &png[BLOCK_SIZE..BLOCK_SIZE * 2]);
+    assert!(score > 0.5 && score < 1.0, "score {score}");
+}
+
 #[test]
 fn pup_empty_seed_set_produces_no_candidates() {
     let data = vec![0u8; BLOCK_SIZE * 4];
@@ -417,6 +823,71 @@ fn pup_empty_seed_set_produces_no_candidates() {
     assert!(cands.is_empty());
 }
 
+#[test]
+fn cluster_estimate_picks_largest_size_most_headers_align_to() {
+    let mut offsets: Vec<u64> = (1..=18).map(|i| i * 3 * 4096).collect();
+    offsets.extend([5 * 512, 1_000_003, 77]);
+    let estimate = cluster::estimate(&offsets, 512).expect("estimate");
+    assert_eq!(estimate.size, 4096);
+    assert_eq!(estimate.headers, 19);
+}
+
+#[test]
+fn cluster_estimate_ignores_unaligned_embedded_headers() {
+    let mut offsets: Vec<u64> = (1..=10).map(|i| i * 65536).collect();
+    offsets.extend((1..=10).map(|i| i * 65536 + 0x1F3));
+    let estimate = cluster::estimate(&offsets, 512).expect("estimate");
+    assert_eq!(estimate.size, 65536);
+    assert_eq!(estimate.headers, 10);
+}
+
+#[test]
+fn cluster_estimate_never_goes_below_the_sector_size() {
+    let offsets: Vec<u64> = (1..=12).map(|i| i * 4096 * 3).collect();
+    let estimate = cluster::estimate(&offsets, 4096).expect("estimate");
+    assert_eq!(estimate.size, 4096);
+}
+
+#[test]
+fn hdd_scan_keeps_the_cluster_size_with_a_thumbnail_and_an_off_grid_file() {
+    let jpeg = minimal_baseline_jpeg();
+    let camera = jpeg_with_exif_thumbnail();
+    let thumbnail = camera
+        .windows(2)
+        .skip(1)
+        .position(|w| w == JPEG_SOI)
+        .expect("thumbnail soi") as u64
+        + 1;
+    let misaligned = 9 * 4096 + 1024;
+    let mut data = vec![0u8; 11 * 4096];
+    data[..camera.len()].copy_from_slice(&camera);
+    let mut offsets: Vec<usize> = (1..9).map(|i| i * 4096).collect();
+    offsets.push(misaligned);
+    for &offset in &offsets {
+        data[offset..offset + jpeg.len()].copy_from_slice(&jpeg);
+    }
+
+    let scan = argos::carve::hdd::scan(&data, 512, |_, _| true).expect("scan");
+
+    assert_eq!(scan.cluster.map(|estimate| estimate.size), Some(4096));
+    assert!(scan.candidates.iter().any(|c| c.offset == 0));
+    assert!(scan.candidates.iter().all(|c| c.offset != thumbnail));
+    assert!(
+        scan.candidates
+            .iter()
+            .any(|c| c.offset == misaligned as u64 && c.length == Some(jpeg.len() as u64)),
+        "{:?}",
+        scan.candidates
+    );
+}
+
+#[test]
+fn cluster_estimate_needs_enough_sector_aligned_headers() {
+    let offsets: Vec<u64> = (1..=7).map(|i| i * 65536).chain([1, 2, 3, 4]).collect();
+    assert_eq!(cluster::estimate(&offsets, 512), None);
+    assert_eq!(cluster::estimate(&[], 512), None);
+}
+
 #[test]
 fn jpeg_validate_returns_one_for_canonical_baseline() {
     let score = jpeg::validate(&minimal_baseline_jpeg()).expect("validate");
@@ -451,6 +922,51 @@ fn jpeg_validate_marks_progressive_with_partial_score() {
     assert_eq!(score, 0.5);
 }
 
+fn nth_sos_offset(data: &[u8], n: usize) -> usize {
+    data.windows(2)
+        .enumerate()
+        .filter(|(_, w)| w == &[0xFF, MARKER_SOS])
+        .map(|(i, _)| i)
+        .nth(n)
+        .expect("sos")
+}
+
+#[test]
+fn jpeg_validate_walks_every_progressive_scan() {
+    for scans in [2, 5] {
+        let data = progressive_jpeg_with_scans(scans);
+        assert_eq!(
+            jpeg::validate(&data).expect("validate"),
+            0.5,
+            "{scans} scans"
+        );
+        assert!(jpeg::truncate_to_last_complete_scan(&data).is_none());
+    }
+
+    let mut broken = progressive_jpeg_with_scans(5);
+    let third = nth_sos_offset(&broken, 2);
+    broken[third + 1] = 0xC8;
+    assert_eq!(jpeg::validate(&broken).expect("validate"), 0.0);
+}
+
+#[test]
+fn jpeg_truncates_progressive_file_at_last_complete_scan() {
+    let full = progressive_jpeg_with_scans(5);
+    let fourth = nth_sos_offset(&full, 3);
+    let cut = &full[..fourth + 6];
+
+    let truncation = jpeg::truncate_to_last_complete_scan(cut).expect("truncation");
+    assert_eq!(truncation.complete_scans, 3);
+    assert_eq!(truncation.source_length, fourth);
+    assert_eq!(truncation.bytes[..fourth], full[..fourth]);
+    assert_eq!(truncation.bytes[fourth..], JPEG_EOI);
+    assert_eq!(jpeg::validate(&truncation.bytes).expect("validate"), 0.5);
+
+    let first_scan_only = &full[..nth_sos_offset(&full, 0) + 12];
+    assert!(jpeg::truncate_to_last_complete_scan(first_scan_only).is_none());
+    assert!(jpeg::truncate_to_last_complete_scan(&minimal_baseline_jpeg()).is_none());
+}
+
 #[test]
 fn jpeg_validate_accepts_byte_stuffed_entropy_before_eoi() {
     let score = jpeg::validate(&baseline_jpeg_with_stuffed_entropy()).expect("validate");
@@ -507,12 +1023,80 @@ fn jpeg_continuation_score_signals_dense_entropy_as_high() {
     assert!(jpeg::continuation_score(&block) >= 0.5);
 }
 
+#[test]
+fn jpeg_validate_resumes_decoding_across_restart_markers() {
+    let data = dc_only_jpeg(4, 6, 3);
+    assert!(restart_marker_offsets(&data).len() > 1);
+    let score = jpeg::validate(&data).expect("validate");
+    assert_eq!(score, 1.0);
+}
+
+#[test]
+fn jpeg_validate_scores_corrupted_restart_interval_below_one() {
+    let score = jpeg::validate(&restart_jpeg_with_corrupted_tail(4, 6, 3)).expect("validate");
+    assert!(score > 0.0 && score < 1.0, "score {score}");
+}
+
+#[test]
+fn jpeg_truncate_to_last_valid_restart_keeps_intact_mcu_rows() {
+    let data = restart_jpeg_with_corrupted_tail(4, 6, 3);
+    let truncation = jpeg::truncate_to_last_valid_restart(&data).expect("truncation");
+    assert_eq!(truncation.mcu_rows, 3);
+    assert!(truncation.source_length < data.len());
+    assert_eq!(
+        truncation.bytes.len(),
+        truncation.source_length + JPEG_EOI.len()
+    );
+    assert!(truncation.bytes.ends_with(&JPEG_EOI));
+    assert_eq!(jpeg::validate(&truncation.bytes).expect("validate"), 1.0);
+}
+
+#[test]
+fn jpeg_truncate_to_last_valid_restart_output_decodes_with_reduced_height() {
+    let data = restart_jpeg_with_corrupted_tail(4, 6, 2);
+    let truncation = jpeg::truncate_to_last_valid_restart(&data).expect("truncation");
+    let decoded = image::load_from_memory_with_format(&truncation.bytes, image::ImageFormat::Jpeg)
+        .expect("decode");
+    assert_eq!(decoded.width(), 32);
+    assert_eq!(decoded.height(), truncation.mcu_rows as u32 * 8);
+}
+
+#[test]
+fn jpeg_truncate_to_last_valid_restart_declines_intact_scan() {
+    assert!(jpeg::truncate_to_last_valid_restart(&dc_only_jpeg(4, 6, 4)).is_none());
+}
+
+#[test]
+fn jpeg_truncate_to_last_valid_restart_declines_scan_without_restart_interval() {
+    let mut data = dc_only_jpeg(4, 6, 0);
+    let end = data.len() - JPEG_EOI.len();
+    data[end - 8..end].fill(0xFF);
+    assert!(jpeg::truncate_to_last_valid_restart(&data).is_none());
+}
+
+#[test]
+fn jpeg_truncate_to_last_valid_restart_declines_corruption_before_first_restart() {
+    assert!(
+        jpeg::truncate_to_last_valid_restart(&restart_jpeg_with_corrupted_tail(4, 6, 0)).is_none()
+    );
+}
+
+#[test]
+fn jpeg_truncate_to_last_valid_restart_declines_progressive() {
+    assert!(jpeg::truncate_to_last_valid_restart(&progressive_jpeg()).is_none());
+}
+
 proptest! {
     #[test]
     fn jpeg_validate_never_panics(data: Vec<u8>) {
         let _ = jpeg::validate(&data);
     }
 
+    #[test]
+    fn jpeg_truncate_to_last_valid_restart_never_panics(data: Vec<u8>) {
+        let _ = jpeg::truncate_to_last_valid_restart(&data);
+    }
+
     #[test]
     fn jpeg_validate_never_panics_on_soi_eoi_envelope(payload: Vec<u8>) {
         let mut buf = Vec::with_capacity(payload.len() + 4);
@@ -534,6 +1118,41 @@ fn png_validate_accepts_canonical_valid_png() {
     assert_eq!(score, 1.0);
 }
 
+fn fctl(sequence: u32, delay_num: u16, delay_den: u16) -> Vec<u8> {
+    let mut body = sequence.to_be_bytes().to_vec();
+    body.extend_from_slice(&4u32.to_be_bytes());
+    body.extend_from_slice(&4u32.to_be_bytes());
+    body.extend_from_slice(&[0u8; 8]);
+    body.extend_from_slice(&delay_num.to_be_bytes());
+    body.extend_from_slice(&delay_den.to_be_bytes());
+    body.extend_from_slice(&[0, 0]);
+    png_chunk(b"fcTL", &body)
+}
+
+#[test]
+fn png_animation_counts_actl_frames_and_sums_fctl_delays() {
+    let still = rgb_png(4, 4);
+    let inspection = png::inspect(&still).expect("inspect");
+    assert_eq!(inspection.animation, None);
+    assert_eq!(inspection.dimensions, Some((4, 4)));
+
+    let ihdr_end = PNG_SIGNATURE.len() + 12 + 13;
+    let mut actl = 3u32.to_be_bytes().to_vec();
+    actl.extend_from_slice(&0u32.to_be_bytes());
+    let mut apng = still[..ihdr_end].to_vec();
+    apng.extend_from_slice(&png_chunk(b"acTL", &actl));
+    apng.extend_from_slice(&fctl(0, 1, 10));
+    apng.extend_from_slice(&fctl(1, 50, 0));
+    apng.extend_from_slice(&fctl(2, 1000, 1000));
+    apng.extend_from_slice(&still[ihdr_end..]);
+
+    let inspection = png::inspect(&apng).expect("inspect");
+    let animation = inspection.animation.expect("animated");
+    assert_eq!(animation.frames, 3);
+    assert_eq!(animation.duration_ms, 1600);
+    assert_eq!(inspection.score, 1.0);
+}
+
 #[test]
 fn png_validate_rejects_garbage() {
     let score = png::validate(&[0u8; 1024]).expect("validate");
@@ -577,6 +1196,319 @@ fn png_validate_rejects_chunk_declaring_length_beyond_input() {
     assert_eq!(score, 0.0);
 }
 
+fn split_idat_png(width: u32, height: u32, parts: usize) -> (Vec<u8>, Vec<Vec<u8>>) {
+    let compressed = zlib_compress(&rgb_scanlines(width, height));
+    let part_len = compressed.len().div_ceil(parts);
+    let chunks: Vec<Vec<u8>> = compressed.chunks(part_len).map(<[u8]>::to_vec).collect();
+    let ihdr = png_ihdr(width, height, 2, false);
+    (ihdr, chunks)
+}
+
+fn idat_offsets(data: &[u8]) -> Vec<usize> {
+    data.windows(4)
+        .enumerate()
+        .filter(|(_, w)| *w == b"IDAT")
+        .map(|(offset, _)| offset - 4)
+        .collect()
+}
+
+#[test]
+fn png_inflate_idat_accepts_multi_chunk_stream() {
+    let (ihdr, chunks) = split_idat_png(16, 16, 3);
+    let refs: Vec<&[u8]> = chunks.iter().map(Vec::as_slice).collect();
+    let data = png_with_idat_chunks(&ihdr, &refs);
+    let inflation = png::inflate_idat(&data).expect("inflate");
+    assert_eq!(inflation.expected, 16 * (1 + 16 * 3));
+    assert_eq!(inflation.inflated, inflation.expected);
+    assert!(inflation.failure.is_none());
+    assert_eq!(png::validate(&data).expect("validate"), 1.0);
+}
+
+#[test]
+fn png_validate_rejects_garbage_idat_with_recomputed_crc() {
+    let garbage = [0xFFu8; 64];
+    let data = png_with_idat_chunks(&png_ihdr(4, 4, 2, false), &[&garbage]);
+    assert_eq!(png::validate(&data).expect("validate"), 0.0);
+
+    let inflation = png::inflate_idat(&data).expect("inflate");
+    let failure = inflation.failure.expect("failure");
+    assert_eq!(failure.chunk_offset, idat_offsets(&data)[0]);
+    assert_eq!(failure.scanline, 0);
+}
+
+#[test]
+fn png_inflate_idat_reports_last_chunk_when_stream_is_cut_short() {
+    let (ihdr, chunks) = split_idat_png(32, 32, 4);
+    let refs: Vec<&[u8]> = chunks[..3].iter().map(Vec::as_slice).collect();
+    let data = png_with_idat_chunks(&ihdr, &refs);
+    let inflation = png::inflate_idat(&data).expect("inflate");
+    let failure = inflation.failure.expect("failure");
+    assert_eq!(failure.chunk_offset, idat_offsets(&data)[2]);
+    assert!(failure.scanline < 32);
+
+    let score = png::validate(&data).expect("validate");
+    assert!(score < 1.0, "score {score}");
+}
+
+#[test]
+fn png_inflate_idat_reports_scanline_when_ihdr_claims_more_rows() {
+    let compressed = zlib_compress(&rgb_scanlines(8, 4));
+    let data = png_with_idat_chunks(&png_ihdr(8, 8, 2, false), &[&compressed]);
+    let inflation = png::inflate_idat(&data).expect("inflate");
+    assert_eq!(inflation.failure.expect("failure").scanline, 4);
+    assert_eq!(png::validate(&data).expect("validate"), 0.5);
+}
+
+#[test]
+fn png_validate_scores_surplus_idat_data_below_one() {
+    let compressed = zlib_compress(&rgb_scanlines(8, 8));
+    let data = png_with_idat_chunks(&png_ihdr(8, 4, 2, false), &[&compressed]);
+    let score = png::validate(&data).expect("validate");
+    assert!(score < 1.0, "score {score}");
+}
+
+#[test]
+fn png_ihdr_raw_length_accounts_for_adam7_passes() {
+    let progressive = png::Ihdr::parse(&png_ihdr(8, 8, 2, true)).expect("ihdr");
+    let sequential = png::Ihdr::parse(&png_ihdr(8, 8, 2, false)).expect("ihdr");
+    assert_eq!(sequential.raw_length(), 200);
+    assert_eq!(progressive.raw_length(), 207);
+    assert_eq!(progressive.scanlines(), 15);
+}
+
+#[test]
+fn png_ihdr_rejects_invalid_bit_depth_for_color_type() {
+    let mut ihdr = png_ihdr(8, 8, 2, false);
+    ihdr[8] = 4;
+    assert!(png::Ihdr::parse(&ihdr).is_none());
+    let data = png_with_idat_chunks(&ihdr, &[&zlib_compress(&[0u8; 64])]);
+    assert_eq!(png::validate(&data).expect("validate"), 0.0);
+}
+
+#[test]
+fn png_validate_output_decodes_in_reference_decoder() {
+    let data = rgb_png(16, 8);
+    assert_eq!(png::validate(&data).expect("validate"), 1.0);
+    let decoded =
+        image::load_from_memory_with_format(&data, image::ImageFormat::Png).expect("decode");
+    assert_eq!((decoded.width(), decoded.height()), (16, 8));
+}
+
+fn split_idat_refs(chunks: &[Vec<u8>]) -> Vec<&[u8]> {
+    chunks.iter().map(Vec::as_slice).collect()
+}
+
+fn decode_png(data: &[u8]) -> image::DynamicImage {
+    image::load_from_memory_with_format(data, image::ImageFormat::Png).expect("decode")
+}
+
+fn crc_repair(data: &[u8]) -> Option<png::CrcRepair> {
+    png::repair_crc(data, &png::inspect(data).expect("inspect"))
+}
+
+fn truncated_repair(data: &[u8]) -> Option<png::PngRepair> {
+    png::repair_truncated(data, &png::inspect(data).expect("inspect"))
+}
+
+#[test]
+fn png_repair_truncated_renders_recovered_top_rows() {
+    let (ihdr, chunks) = split_idat_png(16, 16, 4);
+    let mut data = png_with_idat_chunks(&ihdr, &split_idat_refs(&chunks));
+    let cut = idat_offsets(&data)[2] + 12;
+    data.truncate(cut);
+
+    let repair = truncated_repair(&data).expect("repair");
+    assert_eq!(repair.source_length, data.len());
+    assert!(repair.scanlines > 0 && repair.scanlines < 16);
+    assert_eq!(png::validate(&repair.bytes).expect("validate"), 1.0);
+
+    let decoded = decode_png(&repair.bytes);
+    assert_eq!((decoded.width(), decoded.height()), (16, repair.scanlines));
+}
+
+#[test]
+fn png_repair_truncated_stops_before_chunk_with_bad_crc() {
+    let (ihdr, chunks) = split_idat_png(16, 16, 4);
+    let mut data = png_with_idat_chunks(&ihdr, &split_idat_refs(&chunks));
+    let third = idat_offsets(&data)[2];
+    data[third + 8] ^= 0xFF;
+
+    let repair = truncated_repair(&data).expect("repair");
+    assert_eq!(repair.source_length, third);
+    assert!(repair.scanlines < 16);
+    assert_eq!(decode_png(&repair.bytes).height(), repair.scanlines);
+}
+
+#[test]
+fn png_repair_truncated_shrinks_ihdr_to_inflated_rows() {
+    let compressed = zlib_compress(&rgb_scanlines(8, 4));
+    let data = png_with_idat_chunks(&png_ihdr(8, 8, 2, false), &[&compressed]);
+    let repair = truncated_repair(&data).expect("repair");
+    assert_eq!(repair.scanlines, 4);
+    assert_eq!(decode_png(&repair.bytes).height(), 4);
+}
+
+#[test]
+fn png_repair_truncated_preserves_palette() {
+    let palette = [0xFF, 0x00, 0x00, 0x00, 0xFF, 0x00, 0x00, 0x00, 0xFF];
+    let raw: Vec<u8> = (0..8u8)
+        .flat_map(|row| [0, row % 3, (row + 1) % 3])
+        .collect();
+    let mut data = Vec::new();
+    data.extend_from_slice(&PNG_SIGNATURE);
+    data.extend_from_slice(&png_chunk(b"IHDR", &png_ihdr(2, 16, 3, false)));
+    data.extend_from_slice(&png_chunk(b"PLTE", &palette));
+    data.extend_from_slice(&png_chunk(b"IDAT", &zlib_compress(&raw)));
+
+    let repair = truncated_repair(&data).expect("repair");
+    assert_eq!(repair.scanlines, 8);
+    let decoded = decode_png(&repair.bytes).to_rgb8();
+    assert_eq!(decoded.get_pixel(0, 0).0, [0xFF, 0x00, 0x00]);
+    assert_eq!(decoded.get_pixel(1, 0).0, [0x00, 0xFF, 0x00]);
+}
+
+#[test]
+fn png_repair_truncated_declines_complete_png() {
+    assert!(truncated_repair(&rgb_png(8, 8)).is_none());
+}
+
+#[test]
+fn png_repair_truncated_declines_interlaced_png() {
+    let data = png_with_idat_chunks(&png_ihdr(8, 8, 2, true), &[&zlib_compress(&[0u8; 20])]);
+    assert!(truncated_repair(&data).is_none());
+}
+
+#[test]
+fn png_repair_truncated_declines_when_no_scanline_survives() {
+    let mut data = rgb_png(16, 16);
+    let idat = idat_offsets(&data)[0];
+    data.truncate(idat + 10);
+    assert!(truncated_repair(&data).is_none());
+}
+
+fn flip_chunk_crc(data: &mut [u8], chunk_offset: usize) {
+    let len = u32::from_be_bytes([
+        data[chunk_offset],
+        data[chunk_offset + 1],
+        data[chunk_offset + 2],
+        data[chunk_offset + 3],
+    ]) as usize;
+    data[chunk_offset + 8 + len] ^= 0xFF;
+}
+
+#[test]
+fn png_repair_crc_rewrites_stale_idat_crc_when_stream_inflates() {
+    let mut data = rgb_png(8, 8);
+    let idat = idat_offsets(&data)[0];
+    flip_chunk_crc(&mut data, idat);
+    assert!(png::validate(&data).expect("validate") < 1.0);
+
+    let repair = crc_repair(&data).expect("repair");
+    assert_eq!(repair.chunks_repaired, 1);
+    assert!(repair.dropped.is_empty());
+    assert_eq!(repair.bytes, rgb_png(8, 8));
+}
+
+#[test]
+fn png_repair_crc_drops_ancillary_chunk_with_bad_crc() {
+    let mut data = Vec::new();
+    data.extend_from_slice(&PNG_SIGNATURE);
+    data.extend_from_slice(&png_chunk(b"IHDR", &png_ihdr(4, 4, 2, false)));
+    let text_offset = data.len();
+    data.extend_from_slice(&png_chunk(b"tEXt", b"Comment\0carved"));
+    data.extend_from_slice(&png_chunk(b"IDAT", &zlib_compress(&rgb_scanlines(4, 4))));
+    data.extend_from_slice(&png_chunk(b"IEND", &[]));
+    flip_chunk_crc(&mut data, text_offset);
+
+    let repair = crc_repair(&data).expect("repair");
+    assert_eq!(repair.chunks_repaired, 0);
+    assert_eq!(repair.dropped, vec![*b"tEXt"]);
+    assert_eq!(png::validate(&repair.bytes).expect("validate"), 1.0);
+}
+
+fn iccp_chunk_data() -> Vec<u8> {
+    let mut profile = vec![0u8; 132];
+    profile[..4].copy_from_slice(&132u32.to_be_bytes());
+    profile[36..40].copy_from_slice(b"acsp");
+    let mut data = b"Display P3\0\0".to_vec();
+    data.extend_from_slice(&zlib_compress(&profile));
+    data
+}
+
+fn png_with_iccp(iccp: &[u8], palette_first: bool) -> (Vec<u8>, usize) {
+    let palette = [0xFF, 0x00, 0x00, 0x00, 0xFF, 0x00];
+    let mut data = PNG_SIGNATURE.to_vec();
+    data.extend_from_slice(&png_chunk(b"IHDR", &png_ihdr(2, 8, 3, false)));
+    let mut header = [png_chunk(b"iCCP", iccp), png_chunk(b"PLTE", &palette)];
+    if palette_first {
+        header.reverse();
+    }
+    let iccp_offset = data.len() + if palette_first { header[0].len() } else { 0 };
+    data.extend(header.concat());
+    let raw: Vec<u8> = (0..8u8).flat_map(|row| [0, row % 2, 0]).collect();
+    data.extend_from_slice(&png_chunk(b"IDAT", &zlib_compress(&raw)));
+    (data, iccp_offset)
+}
+
+#[test]
+fn png_repair_crc_keeps_icc_profile_whose_stream_is_intact() {
+    let iccp = iccp_chunk_data();
+    let (mut data, iccp_offset) = png_with_iccp(&iccp, false);
+    data.extend_from_slice(&png_chunk(b"IEND", &[]));
+    flip_chunk_crc(&mut data, iccp_offset);
+
+    let repair = crc_repair(&data).expect("repair");
+    assert_eq!(repair.chunks_repaired, 1);
+    assert!(repair.dropped.is_empty());
+    let chunks = png::parse_chunks(&repair.bytes).expect("chunks");
+    assert_eq!(&chunks[1].chunk_type, b"iCCP");
+    assert_eq!(chunks[1].data, iccp);
+    assert_eq!(png::validate(&repair.bytes).expect("validate"), 1.0);
+}
+
+#[test]
+fn png_repair_crc_drops_icc_profile_with_damaged_stream() {
+    let mut iccp = iccp_chunk_data();
+    let last = iccp.len() - 1;
+    iccp[last] ^= 0xFF;
+    let (mut data, iccp_offset) = png_with_iccp(&iccp, false);
+    data.extend_from_slice(&png_chunk(b"IEND", &[]));
+    flip_chunk_crc(&mut data, iccp_offset);
+
+    let repair = crc_repair(&data).expect("repair");
+    assert_eq!(repair.chunks_repaired, 0);
+    assert_eq!(repair.dropped, vec![*b"iCCP"]);
+}
+
+#[test]
+fn png_repair_truncated_moves_color_chunks_before_palette_and_lists_dropped() {
+    let iccp = iccp_chunk_data();
+    let (mut data, iccp_offset) = png_with_iccp(&iccp, true);
+    flip_chunk_crc(&mut data, iccp_offset);
+    data.extend_from_slice(&png_chunk(b"tEXt", b"Comment\0after pixels"));
+
+    let repair = truncated_repair(&data).expect("repair");
+    assert_eq!(repair.scanlines, 8);
+    assert_eq!(repair.dropped, vec![*b"tEXt"]);
+    let chunks = png::parse_chunks(&repair.bytes).expect("chunks");
+    let order: Vec<&[u8; 4]> = chunks.iter().map(|c| &c.chunk_type).collect();
+    assert_eq!(order, [b"IHDR", b"iCCP", b"PLTE", b"IDAT", b"IEND"]);
+    assert_eq!(chunks[1].data, iccp);
+}
+
+#[test]
+fn png_repair_crc_declines_when_idat_does_not_inflate() {
+    let mut data = rgb_png(8, 8);
+    let idat = idat_offsets(&data)[0];
+    data[idat + 10] ^= 0xFF;
+    assert!(crc_repair(&data).is_none());
+}
+
+#[test]
+fn png_repair_crc_declines_intact_png() {
+    assert!(crc_repair(&rgb_png(8, 8)).is_none());
+}
+
 #[test]
 fn png_continuation_score_completes_chunk_with_valid_crc() {
     let mut partial = png::PartialChunk::default();
@@ -603,6 +1535,18 @@ proptest! {
         let _ = png::validate(&data);
     }
 
+    #[test]
+    fn png_repair_truncated_never_panics_on_any_prefix(cut in 0usize..400) {
+        let data = rgb_png(12, 12);
+        let _ = truncated_repair(&data[..cut.min(data.len())]);
+    }
+
+    #[test]
+    fn png_inflate_idat_never_panics_on_arbitrary_idat(idat: Vec<u8>, width in 1u32..64, height in 1u32..64) {
+        let data = png_with_idat_chunks(&png_ihdr(width, height, 2, false), &[&idat]);
+        let _ = png::inflate_idat(&data);
+    }
+
     #[test]
     fn png_validate_never_panics_on_signature_envelope(payload: Vec<u8>) {
         let mut buf = Vec::with_capacity(payload.len() + 8);
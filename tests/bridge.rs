@@ -14,9 +14,11 @@ fn argos_io_error_maps_to_bridge_io_kind() {
 fn argos_validation_error_maps_to_bridge_validation_kind() {
     let argos = ArgosError::Validation {
         kind: ValidationKind::MissingSoi,
+        offset: 0,
     };
-    let bridge: BridgeError = argos.into();
+    let bridge: BridgeError = argos.with_offset(4096).into();
     assert!(matches!(bridge.kind, BridgeErrorKind::Validation));
+    assert_eq!(bridge.detail, "MissingSoi at byte 4096");
 }
 
 #[test]
@@ -26,6 +28,18 @@ fn argos_unsupported_maps_to_bridge_unsupported() {
     assert!(matches!(bridge.kind, BridgeErrorKind::Unsupported));
 }
 
+#[test]
+fn argos_source_changed_maps_to_its_own_kind() {
+    let bridge: BridgeError = ArgosError::SourceChanged.into();
+    assert!(matches!(bridge.kind, BridgeErrorKind::SourceChanged));
+}
+
+#[test]
+fn argos_output_full_maps_to_its_own_kind() {
+    let bridge: BridgeError = ArgosError::OutputFull.into();
+    assert!(matches!(bridge.kind, BridgeErrorKind::OutputFull));
+}
+
 #[test]
 fn argos_allocation_carries_details() {
     let argos = ArgosError::Allocation {
@@ -98,3 +112,33 @@ fn scoped_path_resolves_symlinks_to_target_for_scope_check() {
         .expect_err("symlink target outside scope must be denied");
     assert!(matches!(err.kind, BridgeErrorKind::Denied));
 }
+
+#[cfg(target_os = "linux")]
+#[test]
+fn whole_disk_maps_partitions_to_their_parent_disk() {
+    let Ok(disks) = std::fs::read_dir("/sys/block") else {
+        return;
+    };
+    for disk in disks.filter_map(|entry| entry.ok()) {
+        let disk_path = std::fs::canonicalize(disk.path()).expect("canonical disk");
+        let children = std::fs::read_dir(&disk_path).expect("disk entries");
+        let nodes = std::iter::once(disk_path.clone()).chain(
+            children
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.join("partition").exists()),
+        );
+        for node in nodes {
+            let Ok(dev) = std::fs::read_to_string(node.join("dev")) else {
+                continue;
+            };
+            let (major, minor) = dev.trim().split_once(':').expect("major:minor");
+            let dev =
+                rustix::fs::makedev(major.parse().expect("major"), minor.parse().expect("minor"));
+            assert_eq!(
+                argos::bridge::devices::whole_disk(dev),
+                Some(disk_path.clone())
+            );
+        }
+    }
+}
@@ -12,6 +12,12 @@ pub const MARKER_DHT: u8 = 0xC4;
 pub const MARKER_SOF0: u8 = 0xC0;
 pub const MARKER_SOF2: u8 = 0xC2;
 pub const MARKER_SOS: u8 = 0xDA;
+pub const MARKER_DRI: u8 = 0xDD;
+pub const MARKER_RST0: u8 = 0xD0;
+pub const MARKER_APP1: u8 = 0xE1;
+pub const MARKER_APP2: u8 = 0xE2;
+
+const DC_LUMINANCE_BITS: [u8; 16] = [0, 1, 5, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0];
 
 pub fn segment(marker: u8, body: &[u8]) -> Vec<u8> {
     let mut out = Vec::with_capacity(4 + body.len());
@@ -78,6 +84,30 @@ pub fn baseline_jpeg_with_entropy(entropy: &[u8]) -> Vec<u8> {
     data
 }
 
+pub fn jpeg_with_icc_segments(payloads: &[(u8, &[u8])], declared: u8) -> Vec<u8> {
+    let jpeg = minimal_baseline_jpeg();
+    let mut data = jpeg[..2].to_vec();
+    for (sequence, payload) in payloads {
+        let mut body = b"ICC_PROFILE\0".to_vec();
+        body.extend_from_slice(&[*sequence, declared]);
+        body.extend_from_slice(payload);
+        data.extend_from_slice(&segment(MARKER_APP2, &body));
+    }
+    data.extend_from_slice(&jpeg[2..]);
+    data
+}
+
+pub fn jpeg_with_exif_thumbnail() -> Vec<u8> {
+    let mut exif = b"Exif\0\0".to_vec();
+    exif.extend_from_slice(&minimal_baseline_jpeg());
+    let outer = minimal_baseline_jpeg();
+    let mut data = Vec::new();
+    data.extend_from_slice(&JPEG_SOI);
+    data.extend_from_slice(&segment(MARKER_APP1, &exif));
+    data.extend_from_slice(&outer[JPEG_SOI.len()..]);
+    data
+}
+
 pub fn baseline_jpeg_with_nonzero_huffman_selectors() -> Vec<u8> {
     let mut data = Vec::new();
     data.extend_from_slice(&JPEG_SOI);
@@ -124,6 +154,177 @@ pub fn progressive_jpeg() -> Vec<u8> {
     data
 }
 
+pub const PROGRESSIVE_SCAN_ENTROPY: [u8; 5] = [0x12, 0x34, 0xFF, 0x00, 0x56];
+
+pub fn progressive_scan_header(spectral_start: u8, spectral_end: u8) -> Vec<u8> {
+    vec![0x01, 0x01, 0x00, spectral_start, spectral_end, 0x00]
+}
+
+pub fn progressive_jpeg_with_scans(scans: u8) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.extend_from_slice(&JPEG_SOI);
+    data.extend_from_slice(&segment(MARKER_DQT, &baseline_dqt()));
+    data.extend_from_slice(&segment(MARKER_DHT, &single_symbol_dht(0)));
+    data.extend_from_slice(&segment(MARKER_SOF2, &baseline_sof0_8x8_grayscale()));
+    for scan in 0..scans {
+        if scan == 1 {
+            data.extend_from_slice(&segment(MARKER_DHT, &single_symbol_dht(1)));
+        }
+        let (start, end) = if scan == 0 { (0, 0) } else { (scan, scan) };
+        data.extend_from_slice(&segment(MARKER_SOS, &progressive_scan_header(start, end)));
+        data.extend_from_slice(&PROGRESSIVE_SCAN_ENTROPY);
+    }
+    data.extend_from_slice(&JPEG_EOI);
+    data
+}
+
+struct EntropyWriter {
+    out: Vec<u8>,
+    acc: u32,
+    filled: u8,
+}
+
+impl EntropyWriter {
+    fn new() -> Self {
+        Self {
+            out: Vec::new(),
+            acc: 0,
+            filled: 0,
+        }
+    }
+
+    fn put(&mut self, value: u32, len: u8) {
+        for shift in (0..len).rev() {
+            self.acc = (self.acc << 1) | ((value >> shift) & 1);
+            self.filled += 1;
+            if self.filled == 8 {
+                self.emit_byte();
+            }
+        }
+    }
+
+    fn emit_byte(&mut self) {
+        let byte = self.acc as u8;
+        self.out.push(byte);
+        if byte == 0xFF {
+            self.out.push(0x00);
+        }
+        self.acc = 0;
+        self.filled = 0;
+    }
+
+    fn pad_to_byte(&mut self) {
+        while self.filled != 0 {
+            self.put(1, 1);
+        }
+    }
+}
+
+fn dc_luminance_codes() -> Vec<(u32, u8)> {
+    let mut codes = Vec::with_capacity(12);
+    let mut code = 0u32;
+    for (index, &count) in DC_LUMINANCE_BITS.iter().enumerate() {
+        for _ in 0..count {
+            codes.push((code, index as u8 + 1));
+            code += 1;
+        }
+        code <<= 1;
+    }
+    codes
+}
+
+fn dc_luminance_dht() -> Vec<u8> {
+    let mut body = Vec::with_capacity(29);
+    body.push(0x00);
+    body.extend_from_slice(&DC_LUMINANCE_BITS);
+    body.extend(0u8..12);
+    body
+}
+
+fn magnitude_category(value: i32) -> u8 {
+    (32 - value.unsigned_abs().leading_zeros()) as u8
+}
+
+fn encode_dc_difference(writer: &mut EntropyWriter, codes: &[(u32, u8)], diff: i32) {
+    let category = magnitude_category(diff);
+    let (code, len) = codes[category as usize];
+    writer.put(code, len);
+    if category > 0 {
+        let bits = if diff < 0 {
+            (diff - 1) as u32 & ((1 << category) - 1)
+        } else {
+            diff as u32
+        };
+        writer.put(bits, category);
+    }
+}
+
+pub fn dc_only_jpeg(width_blocks: u16, height_blocks: u16, restart_interval: u16) -> Vec<u8> {
+    let codes = dc_luminance_codes();
+    let total = width_blocks as usize * height_blocks as usize;
+    let mut writer = EntropyWriter::new();
+    let mut predictor = 0i32;
+    let mut restart_index = 0u8;
+    for mcu in 0..total {
+        if restart_interval > 0 && mcu > 0 && mcu % restart_interval as usize == 0 {
+            writer.pad_to_byte();
+            writer
+                .out
+                .extend_from_slice(&[0xFF, MARKER_RST0 + restart_index]);
+            restart_index = (restart_index + 1) % 8;
+            predictor = 0;
+        }
+        let row = (mcu / width_blocks as usize) as i32;
+        let col = (mcu % width_blocks as usize) as i32;
+        let dc = ((row * 7 + col * 3) % 64) * 8 - 256;
+        encode_dc_difference(&mut writer, &codes, dc - predictor);
+        predictor = dc;
+        writer.put(0, 1);
+    }
+    writer.pad_to_byte();
+
+    let mut sof = Vec::with_capacity(9);
+    sof.push(0x08);
+    sof.extend_from_slice(&(height_blocks * 8).to_be_bytes());
+    sof.extend_from_slice(&(width_blocks * 8).to_be_bytes());
+    sof.push(0x01);
+    sof.extend_from_slice(&[0x01, 0x11, 0x00]);
+
+    let mut data = Vec::new();
+    data.extend_from_slice(&JPEG_SOI);
+    data.extend_from_slice(&segment(MARKER_DQT, &baseline_dqt()));
+    data.extend_from_slice(&segment(MARKER_DHT, &dc_luminance_dht()));
+    data.extend_from_slice(&segment(MARKER_DHT, &single_symbol_dht(1)));
+    data.extend_from_slice(&segment(MARKER_SOF0, &sof));
+    if restart_interval > 0 {
+        data.extend_from_slice(&segment(MARKER_DRI, &restart_interval.to_be_bytes()));
+    }
+    data.extend_from_slice(&segment(MARKER_SOS, &baseline_sos_single_component()));
+    data.extend_from_slice(&writer.out);
+    data.extend_from_slice(&JPEG_EOI);
+    data
+}
+
+pub fn restart_marker_offsets(jpeg: &[u8]) -> Vec<usize> {
+    jpeg.windows(2)
+        .enumerate()
+        .filter(|(_, w)| w[0] == 0xFF && (MARKER_RST0..MARKER_RST0 + 8).contains(&w[1]))
+        .map(|(offset, _)| offset)
+        .collect()
+}
+
+pub fn restart_jpeg_with_corrupted_tail(
+    width_blocks: u16,
+    height_blocks: u16,
+    from_restart: usize,
+) -> Vec<u8> {
+    let mut data = dc_only_jpeg(width_blocks, height_blocks, width_blocks);
+    let start = restart_marker_offsets(&data)[from_restart];
+    let end = data.len() - JPEG_EOI.len();
+    data[start..end].fill(0x00);
+    data
+}
+
 fn crc32_for(chunk_type: &[u8; 4], data: &[u8]) -> u32 {
     let mut hasher = crc32fast::Hasher::new();
     hasher.update(chunk_type);
@@ -142,19 +343,52 @@ pub fn png_chunk(chunk_type: &[u8; 4], body: &[u8]) -> Vec<u8> {
     out
 }
 
-pub fn valid_png() -> Vec<u8> {
+pub fn zlib_compress(raw: &[u8]) -> Vec<u8> {
+    use std::io::Write;
+    let mut encoder = flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(raw).expect("compress");
+    encoder.finish().expect("finish")
+}
+
+pub fn png_ihdr(width: u32, height: u32, color_type: u8, interlaced: bool) -> Vec<u8> {
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    ihdr.extend_from_slice(&[0x08, color_type, 0x00, 0x00, u8::from(interlaced)]);
+    ihdr
+}
+
+pub fn rgb_scanlines(width: u32, height: u32) -> Vec<u8> {
+    let mut raw = Vec::with_capacity(height as usize * (1 + width as usize * 3));
+    for row in 0..height {
+        raw.push(0x00);
+        for col in 0..width {
+            raw.extend_from_slice(&[(row * 16) as u8, (col * 16) as u8, 0x80]);
+        }
+    }
+    raw
+}
+
+pub fn png_with_idat_chunks(ihdr: &[u8], idat_chunks: &[&[u8]]) -> Vec<u8> {
     let mut data = Vec::new();
     data.extend_from_slice(&PNG_SIGNATURE);
-    let ihdr = [
-        0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x01, 0x08, 0x02, 0x00, 0x00, 0x00,
-    ];
-    data.extend_from_slice(&png_chunk(b"IHDR", &ihdr));
-    let idat = [0x78, 0x9C, 0x63, 0x60, 0x00, 0x00, 0x00, 0x02, 0x00, 0x01];
-    data.extend_from_slice(&png_chunk(b"IDAT", &idat));
+    data.extend_from_slice(&png_chunk(b"IHDR", ihdr));
+    for idat in idat_chunks {
+        data.extend_from_slice(&png_chunk(b"IDAT", idat));
+    }
     data.extend_from_slice(&png_chunk(b"IEND", &[]));
     data
 }
 
+pub fn rgb_png(width: u32, height: u32) -> Vec<u8> {
+    let compressed = zlib_compress(&rgb_scanlines(width, height));
+    png_with_idat_chunks(&png_ihdr(width, height, 2, false), &[&compressed])
+}
+
+pub fn valid_png() -> Vec<u8> {
+    rgb_png(1, 1)
+}
+
 pub fn synthetic_device(
     prefix_garbage: usize,
     padding_garbage: usize,
@@ -188,3 +422,333 @@ pub fn write_to(path: &std::path::Path, data: &[u8]) -> std::io::Result<()> {
     file.write_all(data)?;
     file.flush()
 }
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ParsedFileObject {
+    pub filename: String,
+    pub filesize: u64,
+    pub byte_runs: Vec<(u64, u64, u64)>,
+    pub hashes: Vec<(String, String)>,
+    pub carve_method: String,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ParsedDfxml {
+    pub image_filename: String,
+    pub file_objects: Vec<ParsedFileObject>,
+}
+
+pub fn parse_dfxml(xml: &str) -> ParsedDfxml {
+    use quick_xml::events::Event;
+
+    let mut reader = quick_xml::Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+    let mut parsed = ParsedDfxml::default();
+    let mut path: Vec<String> = Vec::new();
+    let mut hash_type = String::new();
+    loop {
+        match reader.read_event().expect("well-formed dfxml") {
+            Event::Start(e) => {
+                let name = String::from_utf8(e.name().as_ref().to_vec()).expect("utf8 name");
+                if name == "fileobject" {
+                    parsed.file_objects.push(ParsedFileObject::default());
+                }
+                if name == "hashdigest" {
+                    hash_type = attribute(&e, "type");
+                }
+                path.push(name);
+            }
+            Event::Empty(e) => {
+                if e.name().as_ref() == b"byte_run" {
+                    let run = (
+                        attribute(&e, "offset").parse().expect("offset"),
+                        attribute(&e, "img_offset").parse().expect("img_offset"),
+                        attribute(&e, "len").parse().expect("len"),
+                    );
+                    current_file_object(&mut parsed).byte_runs.push(run);
+                }
+            }
+            Event::Text(t) => {
+                let text = t.unescape().expect("text").into_owned();
+                match path.last().map(String::as_str) {
+                    Some("image_filename") => parsed.image_filename = text,
+                    Some("filename") => current_file_object(&mut parsed).filename = text,
+                    Some("filesize") => {
+                        current_file_object(&mut parsed).filesize = text.parse().expect("size")
+                    }
+                    Some("hashdigest") => current_file_object(&mut parsed)
+                        .hashes
+                        .push((hash_type.clone(), text)),
+                    Some("argos:carve_method") => {
+                        current_file_object(&mut parsed).carve_method = text
+                    }
+                    _ => {}
+                }
+            }
+            Event::End(_) => {
+                path.pop();
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    assert!(path.is_empty(), "unclosed elements: {path:?}");
+    parsed
+}
+
+fn attribute(element: &quick_xml::events::BytesStart<'_>, key: &str) -> String {
+    element
+        .attributes()
+        .map(|a| a.expect("attribute"))
+        .find(|a| a.key.as_ref() == key.as_bytes())
+        .map(|a| a.unescape_value().expect("value").into_owned())
+        .unwrap_or_default()
+}
+
+fn current_file_object(parsed: &mut ParsedDfxml) -> &mut ParsedFileObject {
+    parsed.file_objects.last_mut().expect("inside fileobject")
+}
+
+pub const GPT_ESP_TYPE: [u8; 16] = [
+    0x28, 0x73, 0x2A, 0xC1, 0x1F, 0xF8, 0xD2, 0x11, 0xBA, 0x4B, 0x00, 0xA0, 0xC9, 0x3E, 0xC9, 0x3B,
+];
+
+pub fn mbr_sector(entries: &[(u8, u32, u32)]) -> Vec<u8> {
+    let mut sector = vec![0u8; 512];
+    for (slot, &(kind, start, sectors)) in entries.iter().enumerate() {
+        let entry = &mut sector[446 + slot * 16..446 + (slot + 1) * 16];
+        entry[4] = kind;
+        entry[8..12].copy_from_slice(&start.to_le_bytes());
+        entry[12..16].copy_from_slice(&sectors.to_le_bytes());
+    }
+    sector[510] = 0x55;
+    sector[511] = 0xAA;
+    sector
+}
+
+pub fn gpt_head(sector: usize, partitions: &[([u8; 16], u64, u64, &str)]) -> Vec<u8> {
+    let entry_count = 128usize;
+    let entry_len = 128usize;
+    let entries_lba = 2usize;
+    let mut head = vec![0u8; entries_lba * sector + entry_count * entry_len];
+    head[..512].copy_from_slice(&mbr_sector(&[(0xEE, 1, u32::MAX)]));
+
+    let entries_start = entries_lba * sector;
+    for (slot, (type_guid, first, last, name)) in partitions.iter().enumerate() {
+        let entry = &mut head[entries_start + slot * entry_len..][..entry_len];
+        entry[..16].copy_from_slice(type_guid);
+        entry[16] = slot as u8 + 1;
+        entry[32..40].copy_from_slice(&first.to_le_bytes());
+        entry[40..48].copy_from_slice(&last.to_le_bytes());
+        for (i, unit) in name.encode_utf16().enumerate() {
+            entry[56 + i * 2..58 + i * 2].copy_from_slice(&unit.to_le_bytes());
+        }
+    }
+    let entries_crc = crc32fast::hash(&head[entries_start..]);
+
+    let header = &mut head[sector..sector + 92];
+    header[..8].copy_from_slice(b"EFI PART");
+    header[8..12].copy_from_slice(&0x0001_0000u32.to_le_bytes());
+    header[12..16].copy_from_slice(&92u32.to_le_bytes());
+    header[24..32].copy_from_slice(&1u64.to_le_bytes());
+    header[72..80].copy_from_slice(&(entries_lba as u64).to_le_bytes());
+    header[80..84].copy_from_slice(&(entry_count as u32).to_le_bytes());
+    header[84..88].copy_from_slice(&(entry_len as u32).to_le_bytes());
+    header[88..92].copy_from_slice(&entries_crc.to_le_bytes());
+    let header_crc = crc32fast::hash(header);
+    header[16..20].copy_from_slice(&header_crc.to_le_bytes());
+    head
+}
+
+pub const LVM_PV_UUID: &str = "Xr0x2VQk1bLy4W7c3Cbc8DeU9fHs2TqA";
+pub const LVM_MDA_OFFSET: usize = 4096;
+
+pub const LVM_METADATA: &str = r#"vg0 {
+id = "fmJ8Yd-2Qh3-oP5q-aB6c-Dd7e-Ff8g-Gh9iJk"
+seqno = 4
+format = "lvm2" # informational
+status = ["RESIZEABLE", "READ", "WRITE"]
+flags = []
+extent_size = 8192
+max_lv = 0
+max_pv = 0
+metadata_copies = 0
+
+physical_volumes {
+
+pv0 {
+id = "Xr0x2V-Qk1b-Ly4W-7c3C-bc8D-eU9f-Hs2TqA"
+device = "/dev/sdb"
+
+status = ["ALLOCATABLE"]
+flags = []
+dev_size = 4194304
+pe_start = 2048
+pe_count = 511
+}
+
+pv1 {
+id = "Lm3nOp-Qr4s-Tu5v-Wx6y-Za7b-Cd8e-Fg9hIj"
+device = "/dev/sdc"
+
+status = ["ALLOCATABLE"]
+flags = []
+dev_size = 4194304
+pe_start = 2048
+pe_count = 511
+}
+}
+
+logical_volumes {
+
+root {
+id = "Rt1aBc-2dEf-3gHi-4jKl-5mNo-6pQr-7sTuVw"
+status = ["READ", "WRITE", "VISIBLE"]
+flags = []
+creation_time = 1700000000
+creation_host = "forensics-lab"
+segment_count = 2
+
+segment1 {
+start_extent = 0
+extent_count = 64
+
+type = "striped"
+stripe_count = 1
+
+stripes = [
+"pv0", 0
+]
+}
+segment2 {
+start_extent = 64
+extent_count = 32
+
+type = "striped"
+stripe_count = 1
+
+stripes = [
+"pv1", 0
+]
+}
+}
+
+home {
+id = "Hm1aBc-2dEf-3gHi-4jKl-5mNo-6pQr-7sTuVw"
+status = ["READ", "WRITE", "VISIBLE"]
+flags = []
+segment_count = 1
+
+segment1 {
+start_extent = 0
+extent_count = 128
+
+type = "striped"
+stripe_count = 1
+
+stripes = [
+"pv0", 64
+]
+}
+}
+
+scratch {
+id = "Sc1aBc-2dEf-3gHi-4jKl-5mNo-6pQr-7sTuVw"
+status = ["READ", "WRITE", "VISIBLE"]
+flags = []
+segment_count = 1
+
+segment1 {
+start_extent = 0
+extent_count = 16
+type = "striped"
+stripe_count = 2
+stripe_size = 128
+
+stripes = [
+"pv0", 192,
+"pv1", 32
+]
+}
+}
+}
+
+}
+# Generated by LVM2 version 2.03.16(2) (2022-05-18): Thu Nov 16 10:00:00 2023
+
+contents = "Text Format Volume Group"
+version = 1
+
+description = "Write from lvcreate -n scratch -i 2 -l 16 vg0."
+
+creation_host = "forensics-lab"	# Linux forensics-lab 6.1.0 #1 SMP x86_64
+creation_time = 1700000000	# Thu Nov 16 10:00:00 2023
+"#;
+
+pub fn lvm_physical_volume(text: &[u8], text_offset: usize, area_size: usize) -> Vec<u8> {
+    let mut head = vec![0u8; LVM_MDA_OFFSET + area_size];
+    let label = 512;
+    head[label..label + 8].copy_from_slice(b"LABELONE");
+    head[label + 8..label + 16].copy_from_slice(&1u64.to_le_bytes());
+    head[label + 20..label + 24].copy_from_slice(&32u32.to_le_bytes());
+    head[label + 24..label + 32].copy_from_slice(b"LVM2 001");
+
+    let pv = label + 32;
+    head[pv..pv + 32].copy_from_slice(LVM_PV_UUID.as_bytes());
+    head[pv + 32..pv + 40].copy_from_slice(&(2u64 << 30).to_le_bytes());
+    let locations = [
+        (1u64 << 20, 0u64),
+        (0, 0),
+        (LVM_MDA_OFFSET as u64, area_size as u64),
+    ];
+    for (i, (offset, size)) in locations.iter().enumerate() {
+        let at = pv + 40 + i * 16;
+        head[at..at + 8].copy_from_slice(&offset.to_le_bytes());
+        head[at + 8..at + 16].copy_from_slice(&size.to_le_bytes());
+    }
+
+    let mda = LVM_MDA_OFFSET;
+    head[mda + 4..mda + 20].copy_from_slice(b" LVM2 x[5A%r0N*>");
+    head[mda + 20..mda + 24].copy_from_slice(&1u32.to_le_bytes());
+    head[mda + 24..mda + 32].copy_from_slice(&(mda as u64).to_le_bytes());
+    head[mda + 32..mda + 40].copy_from_slice(&(area_size as u64).to_le_bytes());
+    head[mda + 40..mda + 48].copy_from_slice(&(text_offset as u64).to_le_bytes());
+    head[mda + 48..mda + 56].copy_from_slice(&(text.len() as u64).to_le_bytes());
+
+    let first = text.len().min(area_size - text_offset);
+    head[mda + text_offset..mda + text_offset + first].copy_from_slice(&text[..first]);
+    head[mda + 512..mda + 512 + text.len() - first].copy_from_slice(&text[first..]);
+    head
+}
+
+pub fn luks1_header(cipher_name: &str, cipher_mode: &str) -> Vec<u8> {
+    let mut head = vec![0u8; 592];
+    head[..6].copy_from_slice(b"LUKS\xBA\xBE");
+    head[6..8].copy_from_slice(&1u16.to_be_bytes());
+    head[8..8 + cipher_name.len()].copy_from_slice(cipher_name.as_bytes());
+    head[40..40 + cipher_mode.len()].copy_from_slice(cipher_mode.as_bytes());
+    head
+}
+
+pub fn luks2_header(json: &str) -> Vec<u8> {
+    let header_size = 16384usize;
+    let mut head = vec![0u8; header_size];
+    head[..6].copy_from_slice(b"LUKS\xBA\xBE");
+    head[6..8].copy_from_slice(&2u16.to_be_bytes());
+    head[8..16].copy_from_slice(&(header_size as u64).to_be_bytes());
+    head[4096..4096 + json.len()].copy_from_slice(json.as_bytes());
+    head
+}
+
+pub fn pseudo_random_bytes(len: usize, seed: u64) -> Vec<u8> {
+    let mut state = seed | 1;
+    let mut bytes = Vec::with_capacity(len + 8);
+    while bytes.len() < len {
+        state ^= state >> 12;
+        state ^= state << 25;
+        state ^= state >> 27;
+        bytes.extend_from_slice(&state.wrapping_mul(0x2545_F491_4F6C_DD1D).to_le_bytes());
+    }
+    bytes.truncate(len);
+    bytes
+}
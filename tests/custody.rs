@@ -1,7 +1,24 @@
-use argos::custody::{AuditEntry, AuditLog, BadSectorMap, Operation, Status, hash};
-use serde_json::Value;
+mod common;
+
+use argos::carve::{DeviceClass, ImageFormat};
+use argos::custody::dfxml::{ByteRun, DfxmlDocument, DfxmlSource, FileObject};
+use argos::custody::gallery::Gallery;
+use argos::custody::manifest::{Fragment, Manifest, ManifestEntry, RecoveryMethod};
+use argos::custody::report::{
+    BadSectorSummary, CandidateRecord, CandidateSummary, DeviceFingerprint, DeviceIdentity,
+    DeviceSummary, OutputSpace, SCHEMA_VERSION, ScanReport, ScanTiming, SourceDigest,
+};
+use argos::custody::{
+    AuditEntry, AuditLog, BadSectorMap, Operation, Status, StreamDigest, hash, md5, sha1,
+    unreadable_overlap,
+};
+use argos::io::partition::{Partition, PartitionScheme, PartitionTable};
+use argos::io::volume::{FileSystem, Volume};
+use serde_json::{Value, json};
 use tempfile::tempdir;
 
+use common::parse_dfxml;
+
 #[test]
 fn sha256_is_deterministic_per_input() {
     let a = hash(b"forensic-input");
@@ -157,3 +174,446 @@ fn bad_sector_map_truncates_previous_contents() {
     assert!(csv.contains("99,100"));
     assert!(!csv.contains("1,2"));
 }
+
+fn sample_scan_report() -> ScanReport {
+    ScanReport {
+        schema_version: SCHEMA_VERSION,
+        source_id: "/dev/sdz".into(),
+        device: DeviceSummary {
+            size_bytes: 1 << 20,
+            sector_size: 512,
+            device_class: DeviceClass::Hdd,
+        },
+        fingerprint: Some(DeviceFingerprint {
+            identity: DeviceIdentity {
+                model: Some("WDC WD10EZEX".into()),
+                serial: Some("WD-WCC6Y0000000".into()),
+                firmware: None,
+                wwn: None,
+            },
+            size_bytes: 1 << 20,
+            head_sha256: hex::encode(hash(b"head")),
+            tail_sha256: hex::encode(hash(b"tail")),
+            verified_at_end: true,
+        }),
+        source_digest: Some(SourceDigest {
+            md5: hex::encode(md5(b"device")),
+            sha256: hex::encode(hash(b"device")),
+            zero_filled_bytes: 0,
+        }),
+        timing: ScanTiming {
+            started_at: 1_700_000_000,
+            finished_at: 1_700_000_002,
+            duration_ms: 2_000,
+        },
+        bytes_scanned: 1 << 20,
+        nested_suppressed: 1,
+        zero_bytes_skipped: 0,
+        crc_repaired: 0,
+        cluster_size: None,
+        cancelled: false,
+        candidate_summary: CandidateSummary {
+            jpeg: 1,
+            png: 1,
+            up_to_100_kib: 2,
+            ..CandidateSummary::default()
+        },
+        output_space: Some(OutputSpace {
+            required_bytes: 200,
+            available_bytes: 1 << 30,
+        }),
+        output_full: false,
+        partition_table: Some(PartitionTable {
+            scheme: PartitionScheme::Mbr,
+            logical_sector_size: 512,
+            partitions: vec![Partition {
+                index: 1,
+                offset: 2048,
+                length: 16384,
+                type_id: "0x0C".into(),
+                name: None,
+            }],
+        }),
+        volumes: vec![Volume {
+            offset: 2048,
+            partition: Some(1),
+            filesystem: FileSystem::Fat,
+            luks: None,
+            logical_volumes: Vec::new(),
+        }],
+        bad_sectors: BadSectorSummary {
+            ranges: 1,
+            bytes: 4096,
+            salvaged_sectors: 7,
+        },
+        candidates: vec![
+            CandidateRecord {
+                offset: 4096,
+                length: 2048,
+                format: ImageFormat::Jpeg,
+                score: Some(1.0),
+                width: Some(640),
+                height: Some(480),
+                partition: Some(1),
+                outputs: vec!["deadbeef_4096_2048_1.00.jpg".into()],
+                rejection: None,
+            },
+            CandidateRecord {
+                offset: 65536,
+                length: 512,
+                format: ImageFormat::Png,
+                score: None,
+                width: None,
+                height: None,
+                partition: None,
+                outputs: Vec::new(),
+                rejection: None,
+            },
+        ],
+    }
+}
+
+#[test]
+fn scan_report_json_round_trips() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("scan_report.json");
+    let report = sample_scan_report();
+    report.write_json(&path).expect("write");
+
+    let content = std::fs::read_to_string(&path).expect("read");
+    let parsed: ScanReport = serde_json::from_str(&content).expect("json");
+    assert_eq!(parsed, report);
+
+    let value: Value = serde_json::from_str(&content).expect("value");
+    assert_eq!(value["device"]["device_class"], "hdd");
+    assert_eq!(value["fingerprint"]["serial"], "WD-WCC6Y0000000");
+    assert!(value["fingerprint"]["firmware"].is_null());
+    assert_eq!(value["volumes"][0]["filesystem"], "fat");
+    assert_eq!(value["candidates"][0]["format"], "jpeg");
+    assert!(value["candidates"][1]["score"].is_null());
+}
+
+#[test]
+fn scan_report_csv_lists_one_row_per_candidate() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("scan_report.csv");
+    sample_scan_report().write_csv(&path).expect("write");
+
+    let csv = std::fs::read_to_string(&path).expect("read");
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(
+        lines[0],
+        "offset,length,format,score,width,height,partition,outputs"
+    );
+    assert_eq!(
+        lines[1],
+        "4096,2048,jpeg,1.0000,640,480,1,deadbeef_4096_2048_1.00.jpg"
+    );
+    assert_eq!(lines[2], "65536,512,png,,,,,");
+    assert_eq!(lines.len(), 3);
+}
+
+#[test]
+fn candidate_summary_counts_formats_and_size_buckets() {
+    let mut summary = CandidateSummary::default();
+    for (format, length) in [
+        (ImageFormat::Jpeg, 100 * 1024),
+        (ImageFormat::Jpeg, 100 * 1024 + 1),
+        (ImageFormat::Png, 1024 * 1024),
+        (ImageFormat::Jpeg, 10 * 1024 * 1024),
+        (ImageFormat::Png, 10 * 1024 * 1024 + 1),
+    ] {
+        summary.record(format, length);
+    }
+    assert_eq!(
+        summary,
+        CandidateSummary {
+            jpeg: 3,
+            png: 2,
+            up_to_100_kib: 1,
+            up_to_1_mib: 2,
+            up_to_10_mib: 1,
+            over_10_mib: 1,
+        }
+    );
+}
+
+#[test]
+fn md5_and_sha1_match_reference_vectors() {
+    assert_eq!(hex::encode(md5(b"abc")), "900150983cd24fb0d6963f7d28e17f72");
+    assert_eq!(
+        hex::encode(sha1(b"abc")),
+        "a9993e364706816aba3e25717850c26c9cd0d89d"
+    );
+}
+
+#[test]
+fn dfxml_lists_byte_runs_in_order_with_digests() {
+    let data = b"carved-bytes";
+    let document = DfxmlDocument {
+        source: DfxmlSource {
+            image_filename: "/dev/sdb & <evidence>".into(),
+            image_size: 1 << 20,
+            sector_size: 512,
+        },
+        file_objects: vec![FileObject {
+            filename: "deadbeef_4096_12_1.00.jpg".into(),
+            filesize: data.len() as u64,
+            byte_runs: vec![
+                ByteRun {
+                    file_offset: 0,
+                    img_offset: 4096,
+                    len: 8,
+                },
+                ByteRun {
+                    file_offset: 8,
+                    img_offset: 16384,
+                    len: 4,
+                },
+            ],
+            md5: md5(data),
+            sha1: sha1(data),
+            sha256: hash(data),
+            carve_method: RecoveryMethod::Carved,
+        }],
+    };
+
+    let parsed = parse_dfxml(&document.render());
+    assert_eq!(parsed.image_filename, "/dev/sdb & <evidence>");
+    assert_eq!(parsed.file_objects.len(), 1);
+    let object = &parsed.file_objects[0];
+    assert_eq!(object.filename, "deadbeef_4096_12_1.00.jpg");
+    assert_eq!(object.filesize, 12);
+    assert_eq!(object.byte_runs, vec![(0, 4096, 8), (8, 16384, 4)]);
+    assert_eq!(
+        object.hashes,
+        vec![
+            ("md5".to_string(), hex::encode(md5(data))),
+            ("sha1".to_string(), hex::encode(sha1(data))),
+            ("sha256".to_string(), hex::encode(hash(data))),
+        ]
+    );
+    assert_eq!(object.carve_method, "carved");
+}
+
+#[test]
+fn manifest_writes_one_json_object_per_line() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("manifest.jsonl");
+    let entries = [
+        ManifestEntry {
+            file: "deadbeef_4096_2048_1.00.jpg".into(),
+            source_id: "/dev/sdb".into(),
+            offset: 4096,
+            fragments: vec![Fragment {
+                offset: 4096,
+                length: 2048,
+            }],
+            format: ImageFormat::Jpeg,
+            method: RecoveryMethod::Carved,
+            confidence: 1.0,
+            width: Some(640),
+            height: Some(480),
+            frames: None,
+            duration_ms: None,
+            icc_profile_intact: Some(true),
+            icc_missing_segments: Vec::new(),
+            quality: Some(92),
+            sha256: hex::encode(hash(b"jpeg")),
+            unreadable_bytes: 0,
+            dropped_chunks: Vec::new(),
+            chunks_repaired: 0,
+            recovered_at: 1_700_000_000,
+        },
+        ManifestEntry {
+            file: "cafebabe_65536_512_1.00_partial.png".into(),
+            source_id: "/dev/sdb".into(),
+            offset: 65536,
+            fragments: vec![Fragment {
+                offset: 65536,
+                length: 512,
+            }],
+            format: ImageFormat::Png,
+            method: RecoveryMethod::Truncated,
+            confidence: 1.0,
+            width: None,
+            height: None,
+            frames: Some(3),
+            duration_ms: Some(1500),
+            icc_profile_intact: None,
+            icc_missing_segments: Vec::new(),
+            quality: None,
+            sha256: hex::encode(hash(b"png")),
+            unreadable_bytes: 512,
+            dropped_chunks: vec!["tEXt".into()],
+            chunks_repaired: 0,
+            recovered_at: 1_700_000_001,
+        },
+    ];
+    let mut manifest = Manifest::create(&path).expect("create");
+    for entry in &entries {
+        manifest.append(entry).expect("append");
+    }
+    drop(manifest);
+
+    let content = std::fs::read_to_string(&path).expect("read");
+    let parsed: Vec<ManifestEntry> = content
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("json line"))
+        .collect();
+    assert_eq!(parsed, entries);
+
+    let second: Value = serde_json::from_str(content.lines().nth(1).expect("line")).expect("json");
+    assert_eq!(second["method"], "truncated");
+    assert_eq!(second["format"], "png");
+    assert!(second["width"].is_null());
+    assert_eq!(second["frames"], 3);
+    assert_eq!(second["dropped_chunks"][0], "tEXt");
+}
+
+#[test]
+fn recovery_method_displays_its_manifest_name() {
+    for method in [
+        RecoveryMethod::Carved,
+        RecoveryMethod::Truncated,
+        RecoveryMethod::CrcRepaired,
+    ] {
+        assert_eq!(Value::String(method.to_string()), json!(method));
+    }
+    assert_eq!(RecoveryMethod::CrcRepaired.to_string(), "crc_repaired");
+}
+
+fn manifest_entry(file: &str, method: RecoveryMethod, confidence: f32) -> ManifestEntry {
+    ManifestEntry {
+        file: file.into(),
+        source_id: "/dev/sdb".into(),
+        offset: 4096,
+        fragments: vec![Fragment {
+            offset: 4096,
+            length: 2048,
+        }],
+        format: ImageFormat::Jpeg,
+        method,
+        confidence,
+        width: Some(640),
+        height: Some(480),
+        frames: None,
+        duration_ms: None,
+        icc_profile_intact: None,
+        icc_missing_segments: Vec::new(),
+        quality: None,
+        sha256: String::new(),
+        unreadable_bytes: 0,
+        dropped_chunks: Vec::new(),
+        chunks_repaired: 0,
+        recovered_at: 0,
+    }
+}
+
+#[test]
+fn gallery_links_each_file_and_badges_partial_or_damaged_outputs() {
+    let gallery = Gallery {
+        source_id: "/dev/<sdb>".into(),
+        entries: vec![
+            ManifestEntry {
+                quality: Some(85),
+                ..manifest_entry("aaaa_4096_2048_0.50.jpg", RecoveryMethod::Carved, 0.5)
+            },
+            ManifestEntry {
+                icc_profile_intact: Some(false),
+                icc_missing_segments: vec![2],
+                unreadable_bytes: 512,
+                ..manifest_entry("bbbb_4096_2048_1.00.jpg", RecoveryMethod::Carved, 1.0)
+            },
+            manifest_entry(
+                "cccc_4096_1024_1.00_partial.jpg",
+                RecoveryMethod::Truncated,
+                1.0,
+            ),
+            ManifestEntry {
+                format: ImageFormat::Png,
+                frames: Some(3),
+                duration_ms: Some(1500),
+                ..manifest_entry("dddd_4096_2048_1.00.png", RecoveryMethod::Carved, 1.0)
+            },
+        ],
+    };
+
+    let html = gallery.render();
+    assert!(html.contains("/dev/&lt;sdb&gt;"));
+    assert!(!html.contains("<sdb>"));
+    assert_eq!(html.matches("<figure>").count(), 4);
+    for entry in &gallery.entries {
+        assert!(html.contains(&format!("<a href=\"{}\">", entry.file)));
+    }
+    assert_eq!(html.matches("class=\"badge damaged\"").count(), 1);
+    assert_eq!(html.matches("class=\"badge partial\"").count(), 1);
+    assert_eq!(html.matches("class=\"badge icc-incomplete\"").count(), 1);
+    assert!(html.contains("quality ~85 &middot; "));
+    assert!(html.contains("640&times;480"));
+    assert!(html.contains("3 frames / 1.5 s"));
+    assert_eq!(html.matches(" frames").count(), 1);
+    assert!(!html.contains("<script"));
+}
+
+#[test]
+fn stream_digest_hashes_gaps_and_tail_as_zeros() {
+    let mut expected = vec![0u8; 300_000];
+    expected[..1000].fill(0x11);
+    expected[70_000..71_000].fill(0x22);
+
+    let mut digest = StreamDigest::new();
+    digest.update(0, &expected[..1000]);
+    digest.update(70_000, &expected[70_000..71_000]);
+    digest.update(500, &[0xFF; 10]);
+    let summary = digest.finish(expected.len() as u64);
+
+    assert_eq!(summary.md5, hex::encode(md5(&expected)));
+    assert_eq!(summary.sha256, hex::encode(hash(&expected)));
+    assert_eq!(summary.zero_filled_bytes, expected.len() as u64 - 2000);
+}
+
+#[test]
+fn bad_sector_overlap_counts_bytes_inside_the_range() {
+    let mut map = BadSectorMap::new();
+    map.record(1000, 100);
+    map.record(5000, 10);
+    let ranges = map.coalesced();
+    assert_eq!(unreadable_overlap(&ranges, 0, 1000), 0);
+    assert_eq!(unreadable_overlap(&ranges, 1050, 100), 50);
+    assert_eq!(unreadable_overlap(&ranges, 900, 5000), 110);
+    assert_eq!(unreadable_overlap(&ranges, 6000, 10), 0);
+}
+
+#[test]
+fn bad_sector_overlap_counts_overlapping_entries_once() {
+    let mut map = BadSectorMap::new();
+    map.record(1000, 512);
+    map.record(1000, 512);
+    map.record(1256, 512);
+    map.record(4096, 512);
+    let ranges = map.coalesced();
+    assert_eq!(unreadable_overlap(&ranges, 0, 2048), 768);
+    assert_eq!(unreadable_overlap(&ranges, 1200, 2000), 568);
+}
+
+#[test]
+fn bad_sector_map_coalesces_adjacent_and_overlapping_reads() {
+    let mut map = BadSectorMap::new();
+    map.record(8192, 4096);
+    map.record(0, 4096);
+    map.record(4096, 4096);
+    map.record(10000, 100);
+    map.record(65536, 512);
+    assert_eq!(map.coalesced(), vec![(0, 12288), (65536, 512)]);
+}
+
+#[test]
+fn bad_sector_map_saturates_ranges_that_run_past_the_address_space() {
+    let mut map = BadSectorMap::new();
+    map.record(u64::MAX - 100, 50);
+    map.record(u64::MAX - 80, 1000);
+    let ranges = map.coalesced();
+    assert_eq!(ranges, vec![(u64::MAX - 100, 100)]);
+    assert_eq!(unreadable_overlap(&ranges, u64::MAX - 60, 1000), 60);
+}
@@ -1,14 +1,41 @@
 mod common;
 
-use argos::bridge::runner::{run_test, run_test_with_device_class};
+use argos::bridge::ProgressPhase;
+use argos::bridge::runner::{
+    run_test, run_test_cancelled_after_artifacts, run_test_with_device_class,
+};
 use argos::carve::DeviceClass;
-use argos::error::ArgosError;
+use argos::custody::manifest::{ManifestEntry, RecoveryMethod};
+use argos::custody::report::{Rejection, SCHEMA_VERSION, ScanReport};
+use argos::custody::{hash, md5, sha1};
+use argos::error::{ArgosError, ValidationKind};
+use argos::io::volume::{FileSystem, Volume};
 use serde_json::Value;
 use std::collections::HashSet;
+use std::os::unix::fs::FileExt;
 use std::path::Path;
 use tempfile::tempdir;
 
-use common::{minimal_baseline_jpeg, sector_aligned_device, synthetic_device, valid_png, write_to};
+use common::{
+    LVM_METADATA, PNG_SIGNATURE, baseline_jpeg_with_entropy, jpeg_with_exif_thumbnail,
+    jpeg_with_icc_segments, lvm_physical_volume, mbr_sector, minimal_baseline_jpeg, parse_dfxml,
+    png_chunk, png_ihdr, png_with_idat_chunks, progressive_jpeg_with_scans, pseudo_random_bytes,
+    restart_jpeg_with_corrupted_tail, rgb_png, rgb_scanlines, sector_aligned_device,
+    synthetic_device, valid_png, write_to, zlib_compress,
+};
+
+const SSD_READ_BLOCK: usize = 1024 * 1024;
+
+const CUSTODY_FILES: &[&str] = &[
+    "audit.log",
+    "bad_sectors.csv",
+    "bad_sectors.map",
+    "scan_report.json",
+    "scan_report.csv",
+    "dfxml.xml",
+    "manifest.jsonl",
+    "report.html",
+];
 
 fn try_recover(source: &Path, output: &Path) -> argos::bridge::runner::RecoveryReport {
     match run_test(source, output) {
@@ -52,6 +79,25 @@ fn assert_final_progress_matches_report(report: &argos::bridge::runner::Recovery
     assert_eq!(final_event.bytes_scanned, report.bytes_scanned);
     assert_eq!(final_event.candidates_found, report.candidates_found);
     assert_eq!(final_event.artifacts_recovered, report.artifacts_recovered);
+    assert_eq!(final_event.phase, ProgressPhase::Extracting);
+    assert_eq!(final_event.candidates_extracted, report.candidates_found);
+    assert_eq!(final_event.total_bytes, report.bytes_scanned);
+    let phases: Vec<ProgressPhase> = report.progress_events.iter().map(|e| e.phase).collect();
+    let first_extracting = phases
+        .iter()
+        .position(|phase| *phase == ProgressPhase::Extracting)
+        .expect("extraction phase");
+    assert!(first_extracting > 0);
+    assert!(
+        phases[..first_extracting]
+            .iter()
+            .all(|phase| *phase == ProgressPhase::Scanning)
+    );
+    assert!(
+        phases[first_extracting..]
+            .iter()
+            .all(|phase| *phase == ProgressPhase::Extracting)
+    );
 }
 
 #[test]
@@ -113,6 +159,58 @@ fn forced_ssd_pipeline_recovers_known_jpeg_and_png_and_reports_final_counts() {
     assert_empty_bad_sector_map(output_dir.path());
 }
 
+#[test]
+fn forced_ssd_pipeline_recovers_jpeg_with_thumbnail_as_one_file() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let jpeg = jpeg_with_exif_thumbnail();
+    let device = sector_aligned_device(4096, &[(4096, &jpeg)]);
+    write_to(&source_path, &device).expect("write device");
+
+    let report = recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    assert_eq!(report.candidates_found, 1);
+    assert_eq!(report.artifacts_recovered, 1);
+    let names = output_file_names(output_dir.path());
+    let jpegs: Vec<_> = names.iter().filter(|name| name.ends_with(".jpg")).collect();
+    assert_eq!(jpegs.len(), 1, "outputs: {names:?}");
+    let bytes = std::fs::read(output_dir.path().join(jpegs[0])).expect("recovered jpeg");
+    assert_eq!(bytes, jpeg);
+
+    let json = std::fs::read_to_string(output_dir.path().join("scan_report.json")).expect("report");
+    let scan: ScanReport = serde_json::from_str(&json).expect("parse report");
+    assert_eq!(scan.nested_suppressed, 1);
+}
+
+#[test]
+fn forced_ssd_pipeline_recovers_jpeg_whose_eoi_lands_on_a_read_block_boundary() {
+    let placement = 4096;
+    let overhead = baseline_jpeg_with_entropy(&[]).len();
+    for eoi_offset in [SSD_READ_BLOCK - 2, SSD_READ_BLOCK - 1, SSD_READ_BLOCK] {
+        let source_dir = tempdir().expect("tempdir");
+        let output_dir = tempdir().expect("tempdir");
+        let source_path = source_dir.path().join("ssd-device.bin");
+        let mut entropy = vec![0x11u8; eoi_offset + 2 - placement - overhead];
+        entropy[0] = 0x00;
+        let jpeg = baseline_jpeg_with_entropy(&entropy);
+        assert_eq!(placement + jpeg.len() - 2, eoi_offset);
+        let device = sector_aligned_device(4096, &[(placement, &jpeg)]);
+        write_to(&source_path, &device).expect("write device");
+
+        let report = recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+        assert_eq!(report.candidates_found, 1, "eoi at {eoi_offset}");
+        assert_eq!(report.artifacts_recovered, 1, "eoi at {eoi_offset}");
+        let names = output_file_names(output_dir.path());
+        let jpegs: Vec<_> = names.iter().filter(|name| name.ends_with(".jpg")).collect();
+        assert_eq!(jpegs.len(), 1, "eoi at {eoi_offset}: {names:?}");
+        assert!(!jpegs[0].contains("_partial"), "eoi at {eoi_offset}");
+        let bytes = std::fs::read(output_dir.path().join(jpegs[0])).expect("recovered jpeg");
+        assert_eq!(bytes, jpeg, "eoi at {eoi_offset}");
+    }
+}
+
 #[test]
 fn forced_hdd_pipeline_recovers_known_jpeg_and_png_and_reports_candidates() {
     let source_dir = tempdir().expect("tempdir");
@@ -140,6 +238,555 @@ fn forced_hdd_pipeline_recovers_known_jpeg_and_png_and_reports_candidates() {
     assert_empty_bad_sector_map(output_dir.path());
 }
 
+#[test]
+fn forced_ssd_pipeline_writes_partial_jpeg_truncated_at_last_restart_marker() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let jpeg = restart_jpeg_with_corrupted_tail(4, 6, 3);
+    let device = sector_aligned_device(4096, &[(4096, &jpeg)]);
+    write_to(&source_path, &device).expect("write device");
+
+    let report = recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    assert_eq!(report.artifacts_recovered, 2);
+    let names = output_file_names(output_dir.path());
+    let partials: Vec<_> = names
+        .iter()
+        .filter(|name| name.ends_with("_partial.jpg"))
+        .collect();
+    assert_eq!(partials.len(), 1, "outputs: {names:?}");
+    assert!(partials[0].ends_with("_1.00_partial.jpg"));
+
+    let content = std::fs::read_to_string(output_dir.path().join("audit.log")).expect("read");
+    let statuses: Vec<Value> = content
+        .lines()
+        .map(|l| serde_json::from_str::<Value>(l).expect("json"))
+        .filter(|entry| entry["operation"] == "recover")
+        .map(|entry| entry["status"].clone())
+        .collect();
+    assert!(statuses.contains(&Value::from("ok")));
+    assert!(statuses.contains(&Value::from("partial")));
+    assert_final_progress_matches_report(&report);
+}
+
+#[test]
+fn forced_ssd_pipeline_writes_progressive_preview_cut_at_last_complete_scan() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let mut jpeg = progressive_jpeg_with_scans(5);
+    let sos: Vec<usize> = jpeg
+        .windows(2)
+        .enumerate()
+        .filter(|(_, w)| w == &[0xFF, 0xDA])
+        .map(|(i, _)| i)
+        .collect();
+    jpeg[sos[3] + 1] = 0xC8;
+    let device = sector_aligned_device(4096, &[(4096, &jpeg)]);
+    write_to(&source_path, &device).expect("write device");
+
+    let report = recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    assert_eq!(report.artifacts_recovered, 1);
+    let names = output_file_names(output_dir.path());
+    let partials: Vec<_> = names
+        .iter()
+        .filter(|name| name.ends_with("_0.50_partial.jpg"))
+        .collect();
+    assert_eq!(partials.len(), 1, "outputs: {names:?}");
+    let bytes = std::fs::read(output_dir.path().join(partials[0])).expect("partial");
+    assert_eq!(bytes[..sos[2]], jpeg[..sos[2]]);
+    assert_eq!(bytes[sos[2]..], [0xFF, 0xD9]);
+}
+
+#[test]
+fn forced_ssd_pipeline_writes_partial_png_for_short_idat_stream() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let compressed = zlib_compress(&rgb_scanlines(8, 4));
+    let png = png_with_idat_chunks(&png_ihdr(8, 8, 2, false), &[&compressed]);
+    let device = sector_aligned_device(4096, &[(4096, &png)]);
+    write_to(&source_path, &device).expect("write device");
+
+    let report = recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    assert_eq!(report.artifacts_recovered, 2);
+    let names = output_file_names(output_dir.path());
+    assert!(
+        names.iter().any(|name| name.ends_with("_1.00_partial.png")),
+        "outputs: {names:?}"
+    );
+    assert_final_progress_matches_report(&report);
+}
+
+#[test]
+fn pipeline_manifest_records_provenance_for_each_output() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let compressed = zlib_compress(&rgb_scanlines(8, 4));
+    let png = png_with_idat_chunks(&png_ihdr(8, 8, 2, false), &[&compressed]);
+    let device = sector_aligned_device(4096, &[(4096, &png)]);
+    write_to(&source_path, &device).expect("write device");
+
+    let report = recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    let content =
+        std::fs::read_to_string(output_dir.path().join("manifest.jsonl")).expect("manifest");
+    let entries: Vec<ManifestEntry> = content
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("manifest line"))
+        .collect();
+    assert_eq!(entries.len() as u64, report.artifacts_recovered);
+
+    let carved = entries
+        .iter()
+        .find(|e| e.method == RecoveryMethod::Carved)
+        .expect("carved entry");
+    assert!(carved.confidence < 1.0);
+    assert_eq!((carved.width, carved.height), (Some(8), Some(8)));
+
+    let partial = entries
+        .iter()
+        .find(|e| e.method == RecoveryMethod::Truncated)
+        .expect("partial entry");
+    assert_eq!(partial.confidence, 1.0);
+    assert_eq!((partial.width, partial.height), (Some(8), Some(4)));
+
+    for entry in &entries {
+        assert_eq!(entry.offset, 4096);
+        assert_eq!(entry.fragments.len(), 1);
+        assert_eq!(entry.fragments[0].offset, 4096);
+        assert_eq!(entry.source_id, source_path.to_string_lossy());
+        let bytes = std::fs::read(output_dir.path().join(&entry.file)).expect("output");
+        assert_eq!(entry.sha256, hex::encode(hash(&bytes)));
+    }
+}
+
+#[test]
+fn pipeline_flags_outputs_overlapping_ddrescue_unreadable_regions() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("disk.img");
+    let jpeg = minimal_baseline_jpeg();
+    let png = rgb_png(3, 2);
+    let device = sector_aligned_device(4096, &[(4096, &jpeg), (8192, &png)]);
+    write_to(&source_path, &device).expect("write device");
+    let mapfile = format!(
+        "# Mapfile. Created by GNU ddrescue version 1.27\n0x0 + 1\n0x0 0x2000 +\n0x2000 0x10 -\n0x2010 {:#x} +\n",
+        device.len() - 0x2010
+    );
+    write_to(&source_dir.path().join("disk.map"), mapfile.as_bytes()).expect("write map");
+
+    let report = recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+    assert!(report.artifacts_recovered >= 2);
+
+    let bad = std::fs::read_to_string(output_dir.path().join("bad_sectors.csv")).expect("bad map");
+    assert!(bad.lines().any(|line| line == "8192,16"), "bad map: {bad}");
+    let rescue_map =
+        std::fs::read_to_string(output_dir.path().join("bad_sectors.map")).expect("rescue map");
+    assert!(
+        rescue_map.contains("0x00002000  0x00000010  -"),
+        "{rescue_map}"
+    );
+    let scan: ScanReport = serde_json::from_str(
+        &std::fs::read_to_string(output_dir.path().join("scan_report.json")).expect("report"),
+    )
+    .expect("scan report json");
+    assert_eq!((scan.bad_sectors.ranges, scan.bad_sectors.bytes), (1, 16));
+
+    let content =
+        std::fs::read_to_string(output_dir.path().join("manifest.jsonl")).expect("manifest");
+    let entries: Vec<ManifestEntry> = content
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("manifest line"))
+        .collect();
+    let png_entry = entries.iter().find(|e| e.offset == 8192).expect("png");
+    assert_eq!(png_entry.unreadable_bytes, 16);
+    let jpeg_entry = entries.iter().find(|e| e.offset == 4096).expect("jpeg");
+    assert_eq!(jpeg_entry.unreadable_bytes, 0);
+
+    let audit = std::fs::read_to_string(output_dir.path().join("audit.log")).expect("audit");
+    let entries: Vec<Value> = audit
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("audit line"))
+        .collect();
+    assert!(entries.iter().any(|e| {
+        e["operation"] == "read"
+            && e["status"] == "ok"
+            && e["source_id"]
+                .as_str()
+                .is_some_and(|s| s.ends_with("disk.map"))
+    }));
+    let png_recover = entries
+        .iter()
+        .find(|e| e["operation"] == "recover" && e["byte_range"][0] == 8192)
+        .expect("png recover entry");
+    assert_eq!(png_recover["status"], "partial");
+}
+
+#[test]
+fn forced_ssd_pipeline_writes_repaired_png_for_stale_idat_crc() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let mut png = valid_png();
+    let idat_crc_end = png.len() - 12;
+    png[idat_crc_end - 1] ^= 0xFF;
+    let device = sector_aligned_device(4096, &[(4096, &png)]);
+    write_to(&source_path, &device).expect("write device");
+
+    let report = recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    assert_eq!(report.artifacts_recovered, 2);
+    let names = output_file_names(output_dir.path());
+    let repaired: Vec<_> = names
+        .iter()
+        .filter(|name| name.ends_with("_1.00_repaired.png"))
+        .collect();
+    assert_eq!(repaired.len(), 1, "outputs: {names:?}");
+    let bytes = std::fs::read(output_dir.path().join(repaired[0])).expect("read repaired");
+    assert_eq!(bytes, valid_png());
+
+    let manifest =
+        std::fs::read_to_string(output_dir.path().join("manifest.jsonl")).expect("manifest");
+    let entry: ManifestEntry = manifest
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("manifest json"))
+        .find(|entry: &ManifestEntry| entry.method == RecoveryMethod::CrcRepaired)
+        .expect("repaired entry");
+    assert_eq!(entry.chunks_repaired, 1);
+    let content = std::fs::read_to_string(output_dir.path().join("scan_report.json"))
+        .expect("read scan report");
+    let scan: ScanReport = serde_json::from_str(&content).expect("scan report json");
+    assert_eq!(scan.crc_repaired, 1);
+}
+
+#[test]
+fn pipeline_writes_scan_report_matching_recovered_outputs() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let jpeg = minimal_baseline_jpeg();
+    let png = rgb_png(3, 2);
+    let mbr = mbr_sector(&[(0x0C, 16, 16)]);
+    let device = sector_aligned_device(4096, &[(0, &mbr), (4096, &jpeg), (8192, &png)]);
+    write_to(&source_path, &device).expect("write device");
+
+    let report = recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    let content = std::fs::read_to_string(output_dir.path().join("scan_report.json"))
+        .expect("read scan report");
+    let scan: ScanReport = serde_json::from_str(&content).expect("scan report json");
+    assert_eq!(scan.schema_version, SCHEMA_VERSION);
+    assert_eq!(scan.device.device_class, DeviceClass::Ssd);
+    assert_eq!(scan.device.size_bytes, device.len() as u64);
+    assert_eq!(scan.bytes_scanned, report.bytes_scanned);
+    assert!(!scan.cancelled);
+    assert!(!scan.output_full);
+    assert_eq!(scan.candidate_summary.jpeg, 1);
+    assert_eq!(scan.candidate_summary.png, 1);
+    assert_eq!(scan.candidate_summary.up_to_100_kib, 2);
+    let space = scan.output_space.expect("output space");
+    assert_eq!(space.required_bytes, (jpeg.len() + png.len()) as u64);
+    assert!(space.available_bytes > 0);
+    assert_eq!(scan.candidates.len() as u64, report.candidates_found);
+    let fingerprint = scan.fingerprint.as_ref().expect("fingerprint");
+    assert_eq!(fingerprint.size_bytes, device.len() as u64);
+    assert_eq!(fingerprint.head_sha256, hex::encode(hash(&device)));
+    assert_eq!(fingerprint.tail_sha256, fingerprint.head_sha256);
+    assert!(fingerprint.verified_at_end);
+    assert_eq!(fingerprint.identity.serial, None);
+
+    let png_record = scan
+        .candidates
+        .iter()
+        .find(|c| c.offset == 8192)
+        .expect("png record");
+    assert_eq!((png_record.width, png_record.height), (Some(3), Some(2)));
+    assert_eq!(png_record.score, Some(1.0));
+    assert_eq!(png_record.partition, Some(1));
+    let jpeg_record = scan
+        .candidates
+        .iter()
+        .find(|c| c.offset == 4096)
+        .expect("jpeg record");
+    assert_eq!(jpeg_record.partition, None);
+    let table = scan.partition_table.as_ref().expect("partition table");
+    assert_eq!(table.partitions[0].offset, 8192);
+    assert!(scan.volumes.is_empty());
+
+    let names = output_file_names(output_dir.path());
+    let listed: Vec<&String> = scan.candidates.iter().flat_map(|c| &c.outputs).collect();
+    assert_eq!(listed.len() as u64, report.artifacts_recovered);
+    assert!(listed.iter().all(|name| names.contains(*name)));
+
+    let csv = std::fs::read_to_string(output_dir.path().join("scan_report.csv")).expect("csv");
+    assert_eq!(csv.lines().count(), scan.candidates.len() + 1);
+}
+
+#[test]
+fn pipeline_fingerprints_head_and_tail_of_an_unaligned_source() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("disk.img");
+    let device: Vec<u8> = (0..3 * 1024 * 1024 + 100)
+        .map(|i: usize| (i % 241) as u8)
+        .collect();
+    write_to(&source_path, &device).expect("write device");
+
+    recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    let json = std::fs::read_to_string(output_dir.path().join("scan_report.json")).expect("report");
+    let scan: ScanReport = serde_json::from_str(&json).expect("parse report");
+    let fingerprint = scan.fingerprint.expect("fingerprint");
+    let window = 1024 * 1024;
+    assert_eq!(
+        fingerprint.head_sha256,
+        hex::encode(hash(&device[..window]))
+    );
+    assert_eq!(
+        fingerprint.tail_sha256,
+        hex::encode(hash(&device[device.len() - window..]))
+    );
+    assert!(fingerprint.verified_at_end);
+}
+
+#[test]
+fn pipeline_hashes_the_whole_source_during_the_scan() {
+    let jpeg = minimal_baseline_jpeg();
+    let device = sector_aligned_device(4096, &[(4096, &jpeg), (40_000, &[0x5Au8; 100])]);
+    for device_class in [DeviceClass::Ssd, DeviceClass::Hdd] {
+        let source_dir = tempdir().expect("tempdir");
+        let output_dir = tempdir().expect("tempdir");
+        let source_path = source_dir.path().join("disk.img");
+        write_to(&source_path, &device).expect("write device");
+
+        recover_as(&source_path, output_dir.path(), device_class);
+
+        let json =
+            std::fs::read_to_string(output_dir.path().join("scan_report.json")).expect("report");
+        let scan: ScanReport = serde_json::from_str(&json).expect("parse report");
+        let digest = scan.source_digest.expect("source digest");
+        assert_eq!(digest.md5, hex::encode(md5(&device)), "{device_class:?}");
+        assert_eq!(
+            digest.sha256,
+            hex::encode(hash(&device)),
+            "{device_class:?}"
+        );
+        assert_eq!(digest.zero_filled_bytes, 0);
+    }
+}
+
+#[test]
+fn forced_ssd_pipeline_skips_sparse_regions_and_still_digests_them() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("sparse.img");
+    let jpeg = minimal_baseline_jpeg();
+    let png = valid_png();
+    let hole = 4 << 20;
+    let mut device = sector_aligned_device(4096, &[(0, &jpeg)]);
+    let head_len = device.len();
+    device.resize(head_len + hole, 0);
+    device.extend_from_slice(&sector_aligned_device(4096, &[(0, &png)]));
+    let tail = head_len + hole;
+    let file = std::fs::File::create(&source_path).expect("create");
+    file.write_all_at(&device[..head_len], 0).expect("head");
+    file.write_all_at(&device[tail..], tail as u64)
+        .expect("tail");
+    drop(file);
+
+    let report = recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    assert_eq!(report.artifacts_recovered, 2);
+    let json = std::fs::read_to_string(output_dir.path().join("scan_report.json")).expect("report");
+    let scan: ScanReport = serde_json::from_str(&json).expect("parse report");
+    assert!(scan.zero_bytes_skipped >= (hole - (1 << 20)) as u64);
+    assert_eq!(scan.bytes_scanned, device.len() as u64);
+    let digest = scan.source_digest.expect("source digest");
+    assert_eq!(digest.sha256, hex::encode(hash(&device)));
+    assert_eq!(digest.zero_filled_bytes, 0);
+}
+
+#[test]
+fn pipeline_reports_filesystem_of_unpartitioned_source() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let png = rgb_png(3, 2);
+    let device = sector_aligned_device(4096, &[(0x438, &[0x53, 0xEF]), (8192, &png)]);
+    write_to(&source_path, &device).expect("write device");
+
+    recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    let content = std::fs::read_to_string(output_dir.path().join("scan_report.json"))
+        .expect("read scan report");
+    let scan: ScanReport = serde_json::from_str(&content).expect("scan report json");
+    assert!(scan.partition_table.is_none());
+    assert_eq!(
+        scan.volumes,
+        vec![Volume {
+            offset: 0,
+            partition: None,
+            filesystem: FileSystem::Ext,
+            luks: None,
+            logical_volumes: Vec::new(),
+        }]
+    );
+}
+
+#[test]
+fn pipeline_lists_linear_logical_volumes_of_an_lvm_physical_volume() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let pv = lvm_physical_volume(LVM_METADATA.as_bytes(), 8192, 16384);
+    let device = sector_aligned_device(4096, &[(0, &pv)]);
+    write_to(&source_path, &device).expect("write device");
+
+    recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    let content = std::fs::read_to_string(output_dir.path().join("scan_report.json"))
+        .expect("read scan report");
+    let scan: ScanReport = serde_json::from_str(&content).expect("scan report json");
+    assert_eq!(scan.volumes.len(), 1);
+    assert_eq!(scan.volumes[0].filesystem, FileSystem::Lvm2);
+    let names: Vec<&str> = scan.volumes[0]
+        .logical_volumes
+        .iter()
+        .map(|lv| lv.name.as_str())
+        .collect();
+    assert_eq!(names, ["root", "home"]);
+}
+
+#[test]
+fn pipeline_reports_where_a_rejected_candidate_failed_to_parse() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let mut png = PNG_SIGNATURE.to_vec();
+    png.extend_from_slice(&png_chunk(b"tEXt", b"k\0v"));
+    png.extend_from_slice(&png_chunk(b"IEND", &[]));
+    let device = sector_aligned_device(4096, &[(4096, &png)]);
+    write_to(&source_path, &device).expect("write device");
+
+    recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    let json = std::fs::read_to_string(output_dir.path().join("scan_report.json")).expect("report");
+    let scan: ScanReport = serde_json::from_str(&json).expect("parse report");
+    assert_eq!(scan.candidates.len(), 1);
+    assert_eq!(
+        scan.candidates[0].rejection,
+        Some(Rejection {
+            kind: ValidationKind::MissingIhdr,
+            offset: 4096 + 8,
+        })
+    );
+    assert!(scan.candidates[0].outputs.is_empty());
+}
+
+#[test]
+fn pipeline_stops_extracting_and_reports_when_the_output_is_full() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let jpeg = minimal_baseline_jpeg();
+    let png = valid_png();
+    let device = sector_aligned_device(4096, &[(4096, &jpeg), (8192, &png)]);
+    write_to(&source_path, &device).expect("write device");
+    let name = format!(
+        "{}_4096_{}_1.00.jpg",
+        hex::encode(&hash(&jpeg)[..4]),
+        jpeg.len()
+    );
+    let blocked = output_dir.path().join(&name);
+    std::os::unix::fs::symlink("/dev/full", &blocked).expect("symlink");
+
+    let result = run_test_with_device_class(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    assert!(matches!(result, Err(ArgosError::OutputFull)));
+    assert!(std::fs::symlink_metadata(&blocked).is_err());
+    let names = output_file_names(output_dir.path());
+    assert!(!names.iter().any(|n| n.ends_with(".png")), "{names:?}");
+    let json = std::fs::read_to_string(output_dir.path().join("scan_report.json")).expect("report");
+    let scan: ScanReport = serde_json::from_str(&json).expect("parse report");
+    assert!(scan.output_full);
+    assert_eq!(scan.candidates.len(), 2);
+    assert!(scan.candidates.iter().all(|c| c.outputs.is_empty()));
+    let audit = std::fs::read_to_string(output_dir.path().join("audit.log")).expect("audit");
+    assert!(
+        audit
+            .lines()
+            .any(|line| line.contains(&name) && line.contains("\"error\""))
+    );
+}
+
+#[test]
+fn pipeline_writes_dfxml_file_object_per_recovered_output() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let jpeg = minimal_baseline_jpeg();
+    let png = rgb_png(3, 2);
+    let device = sector_aligned_device(4096, &[(4096, &jpeg), (8192, &png)]);
+    write_to(&source_path, &device).expect("write device");
+
+    let report = recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    let xml = std::fs::read_to_string(output_dir.path().join("dfxml.xml")).expect("read dfxml");
+    let dfxml = parse_dfxml(&xml);
+    assert_eq!(dfxml.file_objects.len() as u64, report.artifacts_recovered);
+    for object in &dfxml.file_objects {
+        let bytes = std::fs::read(output_dir.path().join(&object.filename)).expect("output");
+        assert_eq!(object.filesize, bytes.len() as u64);
+        assert_eq!(object.carve_method, "carved");
+        assert_eq!(object.byte_runs.len(), 1);
+        let (file_offset, img_offset, len) = object.byte_runs[0];
+        assert_eq!(file_offset, 0);
+        assert_eq!(
+            &device[img_offset as usize..(img_offset + len) as usize],
+            bytes.as_slice()
+        );
+        assert!(
+            object
+                .hashes
+                .contains(&("md5".to_string(), hex::encode(md5(&bytes))))
+        );
+        assert!(
+            object
+                .hashes
+                .contains(&("sha1".to_string(), hex::encode(sha1(&bytes))))
+        );
+    }
+}
+
+#[test]
+fn pipeline_gallery_links_every_recovered_output() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let jpeg = minimal_baseline_jpeg();
+    let png = rgb_png(3, 2);
+    let device = sector_aligned_device(4096, &[(4096, &jpeg), (8192, &png)]);
+    write_to(&source_path, &device).expect("write device");
+
+    let report = recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    let html = std::fs::read_to_string(output_dir.path().join("report.html")).expect("gallery");
+    let outputs: Vec<String> = output_file_names(output_dir.path())
+        .into_iter()
+        .filter(|name| !CUSTODY_FILES.contains(&name.as_str()))
+        .collect();
+    assert_eq!(outputs.len() as u64, report.artifacts_recovered);
+    assert_eq!(html.matches("<figure>").count(), outputs.len());
+    for name in &outputs {
+        assert!(html.contains(&format!("<img src=\"{name}\"")), "{name}");
+    }
+}
+
 #[test]
 fn pipeline_emits_open_recover_close_audit_chain() {
     let source_dir = tempdir().expect("tempdir");
@@ -167,6 +814,91 @@ fn pipeline_emits_open_recover_close_audit_chain() {
     assert!(last["previous_hash"].is_string());
 }
 
+#[test]
+fn cancelling_during_extraction_keeps_written_outputs_and_closes_the_audit_as_partial() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let jpeg = minimal_baseline_jpeg();
+    let device = sector_aligned_device(4096, &[(0, &jpeg), (8192, &jpeg), (16384, &jpeg)]);
+    write_to(&source_path, &device).expect("write device");
+
+    let report =
+        run_test_cancelled_after_artifacts(&source_path, output_dir.path(), DeviceClass::Ssd, 1)
+            .expect("cancelled recovery");
+    assert_eq!(report.artifact_events.len(), 1);
+
+    let manifest =
+        std::fs::read_to_string(output_dir.path().join("manifest.jsonl")).expect("manifest");
+    let entries: Vec<ManifestEntry> = manifest
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("manifest json"))
+        .collect();
+    assert_eq!(entries.len(), 1);
+    assert!(output_dir.path().join(&entries[0].file).exists());
+    let jpg_count = output_file_names(output_dir.path())
+        .iter()
+        .filter(|name| name.ends_with(".jpg"))
+        .count();
+    assert_eq!(jpg_count, 1);
+
+    let content = std::fs::read_to_string(output_dir.path().join("scan_report.json"))
+        .expect("read scan report");
+    let scan: ScanReport = serde_json::from_str(&content).expect("scan report json");
+    assert!(scan.cancelled);
+    assert_eq!(scan.candidates.len(), 3);
+    let written: usize = scan.candidates.iter().map(|c| c.outputs.len()).sum();
+    assert_eq!(written, 1);
+
+    let audit = std::fs::read_to_string(output_dir.path().join("audit.log")).expect("audit");
+    let last: Value =
+        serde_json::from_str(audit.lines().last().expect("close entry")).expect("audit json");
+    assert_eq!(last["operation"], "close");
+    assert_eq!(last["status"], "partial");
+}
+
+#[test]
+fn manifest_lists_missing_icc_profile_segments() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let jpeg = jpeg_with_icc_segments(&[(1, b"AAAA"), (3, b"CC")], 3);
+    let device = sector_aligned_device(4096, &[(4096, &jpeg)]);
+    write_to(&source_path, &device).expect("write device");
+
+    recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    let manifest =
+        std::fs::read_to_string(output_dir.path().join("manifest.jsonl")).expect("manifest");
+    let entries: Vec<ManifestEntry> = manifest
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("manifest json"))
+        .collect();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].icc_profile_intact, Some(false));
+    assert_eq!(entries[0].icc_missing_segments, vec![2]);
+}
+
+#[test]
+fn random_looking_source_raises_an_encryption_warning_and_still_scans() {
+    let source_dir = tempdir().expect("tempdir");
+    let output_dir = tempdir().expect("tempdir");
+    let source_path = source_dir.path().join("ssd-device.bin");
+    let size = 4 * 1024 * 1024;
+    write_to(&source_path, &pseudo_random_bytes(size, 5)).expect("write device");
+
+    let report = recover_as(&source_path, output_dir.path(), DeviceClass::Ssd);
+
+    assert_eq!(report.warning_events.len(), 1);
+    assert_eq!(report.warning_events[0].session_id, 0);
+    assert!(
+        report.warning_events[0].message.contains("looks encrypted"),
+        "{}",
+        report.warning_events[0].message
+    );
+    assert_eq!(report.bytes_scanned, size as u64);
+}
+
 #[test]
 fn pipeline_chains_every_audit_entry_after_the_first() {
     let source_dir = tempdir().expect("tempdir");
@@ -234,7 +966,7 @@ fn recovered_artifact_filenames_carry_format_extension() {
 
     let names = output_file_names(output_dir.path());
     for name in &names {
-        if name == "audit.log" || name == "bad_sectors.csv" {
+        if CUSTODY_FILES.contains(&name.as_str()) {
             continue;
         }
         assert!(
@@ -259,7 +991,7 @@ fn recovered_artifact_filenames_embed_hash_prefix() {
 
     let names = output_file_names(output_dir.path());
     for name in &names {
-        if name == "audit.log" || name == "bad_sectors.csv" {
+        if CUSTODY_FILES.contains(&name.as_str()) {
             continue;
         }
         let prefix: String = name.chars().take(8).collect();
@@ -1,8 +1,21 @@
+mod common;
+
 use argos::error::ArgosError;
-use argos::io::{AlignedBuf, OutputSink, SourceDevice};
+use argos::io::ddrescue::{self, BlockStatus};
+use argos::io::encryption::{self, SAMPLE_WINDOWS, WINDOW_BYTES};
+use argos::io::lvm::{self, LinearSegment, LogicalVolume};
+use argos::io::partition::{self, PartitionScheme};
+use argos::io::volume::{self, FileSystem};
+use argos::io::{AlignedBuf, BlockReader, BlockSource, OutputSink, SourceDevice};
+use proptest::prelude::*;
 use std::io::Write;
 use tempfile::tempdir;
 
+use common::{
+    GPT_ESP_TYPE, LVM_MDA_OFFSET, LVM_METADATA, LVM_PV_UUID, gpt_head, luks1_header, luks2_header,
+    lvm_physical_volume, mbr_sector, minimal_baseline_jpeg, pseudo_random_bytes,
+};
+
 fn write_file(path: &std::path::Path, data: &[u8]) {
     let mut file = std::fs::File::create(path).expect("create");
     file.write_all(data).expect("write");
@@ -64,12 +77,27 @@ fn output_sink_creates_directory_and_writes_files() {
     let dir = tempdir().expect("tempdir");
     let nested = dir.path().join("a").join("b").join("c");
     let sink = OutputSink::create(&nested).expect("create sink with nested dirs");
-    let mut writer = sink.create_file("artifact.jpg").expect("create file");
-    writer.write_all(b"hello").expect("write");
-    drop(writer);
+    sink.write_file("artifact.jpg", b"hello")
+        .expect("write file");
 
     let content = std::fs::read(nested.join("artifact.jpg")).expect("read back");
     assert_eq!(content, b"hello");
+    assert!(sink.available_bytes().expect("statvfs") > 0);
+}
+
+#[test]
+fn output_sink_removes_a_file_it_could_not_finish() {
+    let dir = tempdir().expect("tempdir");
+    let sink = OutputSink::create(dir.path()).expect("create sink");
+    let path = dir.path().join("artifact.jpg");
+    std::os::unix::fs::symlink("/dev/full", &path).expect("symlink");
+
+    let err = sink
+        .write_file("artifact.jpg", &[0xAB; 64 * 1024])
+        .expect_err("device is full");
+
+    assert!(matches!(err, ArgosError::Io(ref e) if e.kind() == std::io::ErrorKind::StorageFull));
+    assert!(std::fs::symlink_metadata(&path).is_err());
 }
 
 #[test]
@@ -104,3 +132,633 @@ fn source_device_size_handles_zero_length() {
         assert_eq!(size, 0);
     }
 }
+
+fn patterned(len: usize) -> Vec<u8> {
+    (0..len).map(|i| (i % 251) as u8).collect()
+}
+
+#[test]
+fn source_device_reads_unaligned_offsets_and_lengths() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("device.bin");
+    let data = patterned(200 * 1024);
+    write_file(&path, &data);
+
+    if let Some(dev) = skip_on_direct_io_unsupported(SourceDevice::open(&path)) {
+        for (offset, len) in [
+            (1usize, 511usize),
+            (4095, 2),
+            (65_530, 12),
+            (70_000, 100_000),
+        ] {
+            let mut buf = vec![0u8; len];
+            let n = dev.read_range(&mut buf, offset as u64).expect("read");
+            assert_eq!(n, len);
+            assert_eq!(buf, data[offset..offset + len]);
+        }
+    }
+}
+
+#[test]
+fn source_device_unaligned_read_is_short_at_end_of_file() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("device.bin");
+    let data = patterned(10_000);
+    write_file(&path, &data);
+
+    if let Some(dev) = skip_on_direct_io_unsupported(SourceDevice::open(&path)) {
+        let mut buf = vec![0u8; 300];
+        let n = dev.read_range(&mut buf, 9_900).expect("read");
+        assert_eq!(n, 100);
+        assert_eq!(buf[..n], data[9_900..]);
+        assert_eq!(dev.read_range(&mut buf, 20_000).expect("read"), 0);
+    }
+}
+
+#[test]
+fn source_device_aligned_reads_bypass_the_shim() {
+    let dir = tempdir().expect("tempdir");
+    let path = dir.path().join("device.bin");
+    let data = patterned(64 * 1024);
+    write_file(&path, &data);
+
+    if let Some(dev) = skip_on_direct_io_unsupported(SourceDevice::open(&path)) {
+        let mut buf = AlignedBuf::with_capacity(8192, 4096).expect("alloc");
+        buf.set_len(8192);
+        let n = dev.read_range(buf.as_mut_slice(), 4096).expect("read");
+        assert_eq!(n, 8192);
+        assert_eq!(buf.as_slice(), &data[4096..12_288]);
+    }
+}
+
+#[test]
+fn classic_mbr_lists_primary_partitions_in_slot_order() {
+    let head = mbr_sector(&[(0x83, 2048, 4096), (0x0C, 8192, 1024)]);
+    let table = partition::parse(&head).expect("mbr");
+    assert_eq!(table.scheme, PartitionScheme::Mbr);
+    assert_eq!(table.partitions.len(), 2);
+    assert_eq!(table.partitions[0].type_id, "0x83");
+    assert_eq!(
+        (table.partitions[0].offset, table.partitions[0].length),
+        (2048 * 512, 4096 * 512)
+    );
+    assert_eq!(table.partitions[1].index, 2);
+    assert_eq!(table.containing(8192 * 512 + 10).map(|p| p.index), Some(2));
+    assert!(table.containing(0).is_none());
+}
+
+#[test]
+fn gpt_entries_are_parsed_with_type_guid_and_name() {
+    for sector in [512usize, 4096] {
+        let head = gpt_head(sector, &[(GPT_ESP_TYPE, 34, 1057, "EFI system")]);
+        let table = partition::parse(&head).expect("gpt");
+        assert_eq!(table.scheme, PartitionScheme::Gpt);
+        assert_eq!(table.logical_sector_size, sector as u64);
+        assert_eq!(table.partitions.len(), 1);
+        let esp = &table.partitions[0];
+        assert_eq!(esp.type_id, "C12A7328-F81F-11D2-BA4B-00A0C93EC93B");
+        assert_eq!(esp.name.as_deref(), Some("EFI system"));
+        assert_eq!(esp.offset, 34 * sector as u64);
+        assert_eq!(esp.length, 1024 * sector as u64);
+    }
+}
+
+#[test]
+fn gpt_with_bad_header_crc_falls_back_to_protective_mbr() {
+    let mut head = gpt_head(512, &[(GPT_ESP_TYPE, 34, 1057, "EFI system")]);
+    head[512 + 40] ^= 0xFF;
+    let table = partition::parse(&head).expect("fallback");
+    assert_eq!(table.scheme, PartitionScheme::Mbr);
+    assert_eq!(table.partitions[0].type_id, "0xEE");
+}
+
+#[test]
+fn volume_boot_sector_is_not_mistaken_for_an_mbr() {
+    let mut head = mbr_sector(&[(0x83, 2048, 4096)]);
+    head[0x52..0x5A].copy_from_slice(b"FAT32   ");
+    assert!(partition::parse(&head).is_none());
+
+    let mut head = mbr_sector(&[(0x07, 2048, 4096)]);
+    head[3..11].copy_from_slice(b"-FVE-FS-");
+    assert!(partition::parse(&head).is_none());
+}
+
+#[test]
+fn unpartitioned_head_has_no_table() {
+    assert!(partition::parse(&[0u8; 4096]).is_none());
+    assert!(partition::parse(&[0u8; 16]).is_none());
+}
+
+fn volume_head(placements: &[(usize, &[u8])]) -> Vec<u8> {
+    let mut head = vec![0u8; volume::PROBE_BYTES as usize];
+    for (offset, bytes) in placements {
+        head[*offset..*offset + bytes.len()].copy_from_slice(bytes);
+    }
+    head
+}
+
+#[test]
+fn volume_probe_recognises_superblock_magics() {
+    let cases: [(Vec<u8>, FileSystem); 12] = [
+        (volume_head(&[(0x438, &[0x53, 0xEF])]), FileSystem::Ext),
+        (volume_head(&[(3, b"NTFS    ")]), FileSystem::Ntfs),
+        (volume_head(&[(3, b"EXFAT   ")]), FileSystem::Exfat),
+        (volume_head(&[(3, b"-FVE-FS-")]), FileSystem::Bitlocker),
+        (
+            volume_head(&[(0x52, b"FAT32   "), (510, &[0x55, 0xAA])]),
+            FileSystem::Fat,
+        ),
+        (
+            volume_head(&[(0x36, b"FAT16   "), (510, &[0x55, 0xAA])]),
+            FileSystem::Fat,
+        ),
+        (volume_head(&[(0x10040, b"_BHRfS_M")]), FileSystem::Btrfs),
+        (volume_head(&[(1024, b"H+\x00\x04")]), FileSystem::Hfsplus),
+        (volume_head(&[(1024, b"HX\x00\x05")]), FileSystem::Hfsplus),
+        (volume_head(&[(32, b"NXSB")]), FileSystem::Apfs),
+        (
+            volume_head(&[(0, &luks1_header("aes", "xts-plain64"))]),
+            FileSystem::Luks,
+        ),
+        (
+            volume_head(&[(0, &lvm_physical_volume(b"", 0, 512)[..1024])]),
+            FileSystem::Lvm2,
+        ),
+    ];
+    for (head, expected) in cases {
+        assert_eq!(volume::probe(&head), Some(expected));
+    }
+}
+
+#[test]
+fn volume_probe_rejects_bare_mbr_and_short_input() {
+    assert_eq!(volume::probe(&mbr_sector(&[(0x83, 2048, 4096)])), None);
+    assert_eq!(
+        volume::probe(&volume_head(&[(0x52, b"FAT32   ")])),
+        None,
+        "FAT label without boot signature"
+    );
+    assert_eq!(
+        volume::probe(&volume_head(&[(1024, b"H+\x00\x05")])),
+        None,
+        "HFS+ signature with HFSX version"
+    );
+    assert_eq!(volume::probe(&[0u8; 8]), None);
+}
+
+#[test]
+fn luks_header_reports_version_and_cipher() {
+    let luks1 = volume::luks_header(&luks1_header("aes", "xts-plain64")).expect("luks1");
+    assert_eq!(luks1.version, 1);
+    assert_eq!(luks1.cipher.as_deref(), Some("aes-xts-plain64"));
+
+    let json = r#"{"keyslots":{},"tokens":{},"segments":{"0":{"type":"crypt","offset":"16777216","size":"dynamic","iv_tweak":"0","encryption":"aes-xts-plain64","sector_size":512}},"digests":{},"config":{"json_size":"12288","keyslots_size":"16744448"}}"#;
+    let luks2 = volume::luks_header(&luks2_header(json)).expect("luks2");
+    assert_eq!(luks2.version, 2);
+    assert_eq!(luks2.cipher.as_deref(), Some("aes-xts-plain64"));
+
+    let damaged = volume::luks_header(&luks2_header("{\"segments\":")).expect("luks2");
+    assert_eq!(damaged.cipher, None);
+    assert!(volume::luks_header(&[0u8; 64]).is_none());
+}
+
+#[test]
+fn lvm_metadata_area_locates_the_current_text() {
+    let text = LVM_METADATA.as_bytes();
+    let head = lvm_physical_volume(text, 8192, 16384);
+    let area = lvm::metadata_area(&head).expect("metadata area");
+    assert_eq!(area.pv_uuid, LVM_PV_UUID);
+    assert_eq!(
+        area.ranges,
+        [((LVM_MDA_OFFSET + 8192) as u64, text.len() as u64)]
+    );
+}
+
+#[test]
+fn lvm_metadata_area_follows_text_that_wraps_the_ring() {
+    let text = LVM_METADATA.as_bytes();
+    let head = lvm_physical_volume(text, 16384 - 100, 16384);
+    let area = lvm::metadata_area(&head).expect("metadata area");
+    let mda = LVM_MDA_OFFSET as u64;
+    assert_eq!(
+        area.ranges,
+        [
+            (mda + 16384 - 100, 100),
+            (mda + 512, text.len() as u64 - 100)
+        ]
+    );
+    let joined: Vec<u8> = area
+        .ranges
+        .iter()
+        .flat_map(|&(start, len)| head[start as usize..(start + len) as usize].to_vec())
+        .collect();
+    assert_eq!(joined, text);
+}
+
+#[test]
+fn lvm_lists_linear_segments_stored_on_this_physical_volume() {
+    let head = lvm_physical_volume(LVM_METADATA.as_bytes(), 512, 16384);
+    let area = lvm::metadata_area(&head).expect("metadata area");
+    let base = 1 << 20;
+    let data = base + 2048 * 512;
+    let extent = 8192 * 512;
+
+    let volumes = lvm::logical_volumes(&area, LVM_METADATA.as_bytes(), base);
+
+    assert_eq!(
+        volumes,
+        [
+            LogicalVolume {
+                name: "root".into(),
+                segments: vec![LinearSegment {
+                    offset: data,
+                    length: 64 * extent,
+                }],
+            },
+            LogicalVolume {
+                name: "home".into(),
+                segments: vec![LinearSegment {
+                    offset: data + 64 * extent,
+                    length: 128 * extent,
+                }],
+            },
+        ]
+    );
+}
+
+#[test]
+fn lvm_rejects_damaged_headers_and_metadata() {
+    let text = LVM_METADATA.as_bytes();
+    let mut head = lvm_physical_volume(text, 512, 16384);
+    head[LVM_MDA_OFFSET + 4] ^= 0xFF;
+    assert!(lvm::has_label(&head));
+    assert!(lvm::metadata_area(&head).is_none());
+
+    let head = lvm_physical_volume(text, 512, 16384);
+    let area = lvm::metadata_area(&head).expect("metadata area");
+    let truncated = &text[..text.len() / 2];
+    assert!(lvm::logical_volumes(&area, truncated, 0).is_empty());
+    let nested = "a {".repeat(64);
+    assert!(lvm::logical_volumes(&area, nested.as_bytes(), 0).is_empty());
+
+    let mut head = lvm_physical_volume(text, 512, 16384);
+    head[LVM_MDA_OFFSET + 32..LVM_MDA_OFFSET + 40].copy_from_slice(&u64::MAX.to_le_bytes());
+    head[LVM_MDA_OFFSET + 40..LVM_MDA_OFFSET + 48].copy_from_slice(&(u64::MAX - 1).to_le_bytes());
+    assert!(lvm::metadata_area(&head).is_none());
+}
+
+#[test]
+fn ddrescue_mapfile_parses_current_syntax_with_comments() {
+    let text = "# Mapfile. Created by GNU ddrescue version 1.27\n\
+# Command line: ddrescue /dev/sdb disk.img disk.map\n\
+# current_pos  current_status  current_pass\n\
+0x00120000     +               1\n\
+#      pos        size  status\n\
+0x00000000  0x00100000  +\n\
+0x00100000  0x00001000  -   # bad\n\
+0x00101000  0x00000200  *\n\
+0x00101200  0x0001EE00  +\n";
+    let blocks = ddrescue::parse(text).expect("mapfile");
+    assert_eq!(blocks.len(), 4);
+    assert_eq!(blocks[1].offset, 0x0010_0000);
+    assert_eq!(blocks[1].status, BlockStatus::BadSector);
+    assert_eq!(
+        ddrescue::unreadable_ranges(&blocks),
+        vec![(0x0010_0000, 0x1200)]
+    );
+}
+
+#[test]
+fn ddrescue_mapfile_accepts_old_status_line_and_decimal_or_octal_numbers() {
+    let text = "0 +\n0 4096 +\n4096 010000 ?\n8192 512 /\n";
+    let blocks = ddrescue::parse(text).expect("mapfile");
+    assert_eq!(blocks[1].length, 4096);
+    assert_eq!(blocks[1].status, BlockStatus::NonTried);
+    assert_eq!(ddrescue::unreadable_ranges(&blocks), vec![(4096, 4608)]);
+}
+
+proptest! {
+    #[test]
+    fn ddrescue_parse_never_panics_on_arbitrary_text(text in "\\PC{0,512}") {
+        if let Some(blocks) = ddrescue::parse(&text) {
+            let _ = ddrescue::unreadable_ranges(&blocks);
+        }
+    }
+
+    #[test]
+    fn ddrescue_render_round_trips_arbitrary_disjoint_ranges(
+        spans in prop::collection::vec((1u64..1 << 30, 1u64..1 << 20), 0..16),
+        tail in 0u64..1 << 20,
+    ) {
+        let mut cursor = 0_u64;
+        let mut ranges = Vec::with_capacity(spans.len());
+        for (gap, length) in spans {
+            ranges.push((cursor + gap, length));
+            cursor += gap + length;
+        }
+        let text = ddrescue::render(&ranges, cursor + tail);
+        let blocks = ddrescue::parse(&text).expect("rendered mapfile");
+        prop_assert_eq!(ddrescue::unreadable_ranges(&blocks), ranges);
+    }
+}
+
+#[test]
+fn ddrescue_mapfile_rejects_malformed_block_lines() {
+    assert!(ddrescue::parse("0x0 +\n0x0 0x200 X\n").is_none());
+    assert!(ddrescue::parse("0x0 +\n0x0 0x200 + extra\n").is_none());
+    assert!(ddrescue::parse("0x0 +\nzz 0x200 +\n").is_none());
+    assert!(ddrescue::parse("0x0 +\n0xFFFFFFFFFFFFFF00 0x200 -\n").is_none());
+    assert!(ddrescue::parse("# only comments\n").is_none());
+}
+
+#[test]
+fn ddrescue_mapfile_is_found_next_to_image() {
+    let dir = tempdir().expect("tempdir");
+    let image = dir.path().join("disk.img");
+    write_file(&image, b"image");
+    assert_eq!(ddrescue::sibling_mapfile(&image), None);
+
+    let appended = dir.path().join("disk.img.map");
+    write_file(&appended, b"0 +\n");
+    assert_eq!(ddrescue::sibling_mapfile(&image), Some(appended));
+
+    let replaced = dir.path().join("disk.map");
+    write_file(&replaced, b"0 +\n");
+    assert_eq!(ddrescue::sibling_mapfile(&image), Some(replaced));
+}
+
+#[test]
+fn ddrescue_render_round_trips_through_parse() {
+    let text = ddrescue::render(&[(4096, 512), (8192, 4096)], 16384);
+    let blocks = ddrescue::parse(&text).expect("rendered mapfile");
+    let summary: Vec<(u64, u64, BlockStatus)> = blocks
+        .iter()
+        .map(|b| (b.offset, b.length, b.status))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            (0, 4096, BlockStatus::Finished),
+            (4096, 512, BlockStatus::BadSector),
+            (4608, 3584, BlockStatus::Finished),
+            (8192, 4096, BlockStatus::BadSector),
+            (12288, 4096, BlockStatus::Finished),
+        ]
+    );
+    assert_eq!(
+        ddrescue::unreadable_ranges(&blocks),
+        vec![(4096, 512), (8192, 4096)]
+    );
+}
+
+#[test]
+fn ddrescue_render_of_clean_device_is_one_finished_block() {
+    let blocks = ddrescue::parse(&ddrescue::render(&[], 8192)).expect("mapfile");
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(
+        (blocks[0].offset, blocks[0].length, blocks[0].status),
+        (0, 8192, BlockStatus::Finished)
+    );
+}
+
+const FAULT_SECTOR: usize = 512;
+
+#[derive(Debug)]
+struct FaultySource {
+    data: Vec<u8>,
+    bad: Vec<u64>,
+    holes: Vec<(u64, u64)>,
+}
+
+impl BlockSource for FaultySource {
+    fn sector_size(&self) -> usize {
+        FAULT_SECTOR
+    }
+
+    fn read_range(&self, buf: &mut [u8], offset: u64) -> Result<usize, ArgosError> {
+        let end = offset + buf.len() as u64;
+        if self.bad.iter().any(|&b| b >= offset && b < end) {
+            return Err(ArgosError::Io(std::io::Error::from_raw_os_error(5)));
+        }
+        let start = (offset as usize).min(self.data.len());
+        let n = buf.len().min(self.data.len() - start);
+        buf[..n].copy_from_slice(&self.data[start..start + n]);
+        Ok(n)
+    }
+
+    fn next_data(&self, offset: u64) -> Option<u64> {
+        let hole = self
+            .holes
+            .iter()
+            .find(|&&(start, length)| offset >= start && offset < start + length);
+        Some(hole.map_or(offset, |&(start, length)| start + length))
+    }
+}
+
+#[derive(Debug)]
+struct ReadOutcome {
+    blocks: Vec<(u64, Vec<u8>)>,
+    bad: Vec<(u64, u64)>,
+    salvaged: u64,
+}
+
+fn read_all(source: &FaultySource, block: usize) -> ReadOutcome {
+    let buf = AlignedBuf::with_capacity(block, FAULT_SECTOR).expect("buf");
+    let mut reader = BlockReader::new(source, buf, source.data.len() as u64);
+    let mut blocks = Vec::new();
+    while let Some((offset, data)) = reader.try_next().expect("read") {
+        blocks.push((offset, data.to_vec()));
+    }
+    ReadOutcome {
+        blocks,
+        bad: reader.bad_sectors().to_vec(),
+        salvaged: reader.salvaged_sectors(),
+    }
+}
+
+#[test]
+fn block_reader_salvages_sectors_around_a_single_bad_sector() {
+    let data: Vec<u8> = (0..8 * FAULT_SECTOR)
+        .map(|i| (i / FAULT_SECTOR) as u8)
+        .collect();
+    let source = FaultySource {
+        data: data.clone(),
+        bad: vec![2 * FAULT_SECTOR as u64],
+        holes: Vec::new(),
+    };
+
+    let ReadOutcome {
+        blocks,
+        bad,
+        salvaged,
+    } = read_all(&source, 4 * FAULT_SECTOR);
+
+    assert_eq!(bad, vec![(1024, 512)]);
+    assert_eq!(salvaged, 3);
+    let offsets: Vec<u64> = blocks.iter().map(|(o, _)| *o).collect();
+    assert_eq!(offsets, vec![0, 1536, 2048]);
+    assert_eq!(blocks[0].1, data[..1024]);
+    assert_eq!(blocks[1].1, data[1536..2048]);
+    assert_eq!(blocks[2].1, data[2048..]);
+}
+
+#[test]
+fn block_reader_skips_whole_blocks_after_a_fully_bad_window() {
+    let data = vec![0x5Au8; 12 * FAULT_SECTOR];
+    let bad: Vec<u64> = (0..8).map(|s| (s * FAULT_SECTOR) as u64).collect();
+    let source = FaultySource {
+        data,
+        bad,
+        holes: Vec::new(),
+    };
+
+    let ReadOutcome {
+        blocks,
+        bad,
+        salvaged,
+    } = read_all(&source, 4 * FAULT_SECTOR);
+
+    assert_eq!(salvaged, 0);
+    assert_eq!(bad.len(), 5);
+    assert_eq!(bad[..4], [(0, 512), (512, 512), (1024, 512), (1536, 512)]);
+    assert_eq!(bad[4], (2048, 2048));
+    assert_eq!(blocks.len(), 1);
+    assert_eq!(blocks[0].0, 4096);
+}
+
+#[test]
+fn block_reader_yields_holes_as_zeros_without_reading_them() {
+    let mut data = vec![0u8; 24 * FAULT_SECTOR];
+    data[..4 * FAULT_SECTOR].fill(0x11);
+    data[16 * FAULT_SECTOR..].fill(0x22);
+    let source = FaultySource {
+        data: data.clone(),
+        bad: (4..16).map(|s| (s * FAULT_SECTOR) as u64).collect(),
+        holes: vec![(2048, 12 * FAULT_SECTOR as u64 + 128)],
+    };
+
+    let ReadOutcome {
+        blocks,
+        bad,
+        salvaged,
+    } = read_all(&source, 4 * FAULT_SECTOR);
+
+    assert!(bad.is_empty(), "hole was read: {bad:?}");
+    assert_eq!(salvaged, 0);
+    let offsets: Vec<u64> = blocks.iter().map(|(o, _)| *o).collect();
+    assert_eq!(offsets, vec![0, 2048, 4096, 6144, 8192, 10240]);
+    let joined: Vec<u8> = blocks.into_iter().flat_map(|(_, b)| b).collect();
+    assert_eq!(joined, data);
+}
+
+#[derive(Debug)]
+struct DribbleSource {
+    data: Vec<u8>,
+}
+
+impl BlockSource for DribbleSource {
+    fn sector_size(&self) -> usize {
+        1
+    }
+
+    fn read_range(&self, buf: &mut [u8], offset: u64) -> Result<usize, ArgosError> {
+        let start = (offset as usize).min(self.data.len());
+        let n = buf.len().min(self.data.len() - start).min(3);
+        buf[..n].copy_from_slice(&self.data[start..start + n]);
+        Ok(n)
+    }
+}
+
+#[test]
+fn read_exact_at_loops_over_short_reads_and_reports_eof() {
+    let source = DribbleSource {
+        data: patterned(100),
+    };
+    let mut buf = [0u8; 40];
+    source.read_exact_at(&mut buf, 50).expect("read");
+    assert_eq!(buf[..], source.data[50..90]);
+
+    let err = source.read_exact_at(&mut buf, 70).expect_err("past end");
+    match err {
+        ArgosError::Io(e) => assert_eq!(e.kind(), std::io::ErrorKind::UnexpectedEof),
+        other => panic!("unexpected error: {other:?}"),
+    }
+}
+
+fn memory_source(data: Vec<u8>) -> FaultySource {
+    FaultySource {
+        data,
+        bad: Vec::new(),
+        holes: Vec::new(),
+    }
+}
+
+const ENCRYPTION_SAMPLE_BYTES: usize = 16 * 1024 * 1024;
+
+#[test]
+fn random_source_without_signatures_looks_encrypted() {
+    let source = memory_source(pseudo_random_bytes(ENCRYPTION_SAMPLE_BYTES, 7));
+    let assessment = encryption::assess(&source, ENCRYPTION_SAMPLE_BYTES as u64).expect("assess");
+    assert_eq!(assessment.sampled_windows, SAMPLE_WINDOWS);
+    assert_eq!(assessment.uniform_windows, SAMPLE_WINDOWS);
+    assert_eq!(assessment.signature_hits, 0);
+    assert!(assessment.looks_encrypted());
+    let warning = assessment.warning().expect("warning");
+    assert!(warning.contains("16 of 16"), "{warning}");
+}
+
+#[test]
+fn high_entropy_source_with_image_headers_is_not_flagged() {
+    let mut data = pseudo_random_bytes(ENCRYPTION_SAMPLE_BYTES, 11);
+    let jpeg = minimal_baseline_jpeg();
+    for start in (4096..data.len()).step_by(WINDOW_BYTES as usize) {
+        data[start..start + jpeg.len()].copy_from_slice(&jpeg);
+    }
+    let source = memory_source(data);
+    let assessment = encryption::assess(&source, ENCRYPTION_SAMPLE_BYTES as u64).expect("assess");
+    assert_eq!(assessment.uniform_windows, SAMPLE_WINDOWS);
+    assert!(assessment.signature_hits > 0);
+    assert!(!assessment.looks_encrypted());
+    assert_eq!(assessment.warning(), None);
+}
+
+#[test]
+fn zero_filled_and_small_sources_are_not_flagged() {
+    let source = memory_source(vec![0u8; ENCRYPTION_SAMPLE_BYTES]);
+    let assessment = encryption::assess(&source, ENCRYPTION_SAMPLE_BYTES as u64).expect("assess");
+    assert_eq!(assessment.sampled_windows, SAMPLE_WINDOWS);
+    assert_eq!(assessment.uniform_windows, 0);
+    assert!(!assessment.looks_encrypted());
+
+    let small = pseudo_random_bytes(3 * WINDOW_BYTES as usize, 3);
+    let assessment = encryption::assess(&memory_source(small), 3 * WINDOW_BYTES).expect("assess");
+    assert_eq!(assessment.sampled_windows, 3);
+    assert!(!assessment.looks_encrypted());
+}
+
+#[test]
+fn encrypted_container_headers_are_reported_per_partition() {
+    let mut data = vec![0u8; 4 * 1024 * 1024];
+    data[..512].copy_from_slice(&mbr_sector(&[(0x83, 2048, 4096)]));
+    let luks = luks1_header("aes", "xts-plain64");
+    data[1 << 20..(1 << 20) + luks.len()].copy_from_slice(&luks);
+    let size = data.len() as u64;
+    let assessment = encryption::assess(&memory_source(data), size).expect("assess");
+    assert_eq!(assessment.containers, [(1 << 20, FileSystem::Luks)]);
+    assert!(assessment.warning().expect("warning").contains("LUKS"));
+
+    let mut data = vec![0u8; 4 * 1024 * 1024];
+    data[3..11].copy_from_slice(b"-FVE-FS-");
+    data[510..512].copy_from_slice(&[0x55, 0xAA]);
+    let size = data.len() as u64;
+    let assessment = encryption::assess(&memory_source(data), size).expect("assess");
+    assert_eq!(assessment.containers, [(0, FileSystem::Bitlocker)]);
+    assert!(assessment.warning().expect("warning").contains("BitLocker"));
+}
+
+#[test]
+fn chi_square_is_zero_for_a_perfectly_flat_histogram() {
+    let flat: Vec<u8> = (0..=255u8).cycle().take(256 * 64).collect();
+    assert_eq!(encryption::chi_square(&flat), 0.0);
+    assert!(encryption::chi_square(&[0u8; 4096]) > 1000.0);
+}